@@ -1,5 +1,16 @@
-use cargo::{core::PackageIdSpec, util::command_prelude::*};
-use cargo_information::ops;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use cargo::{
+    core::{GitReference, PackageIdSpec},
+    util::command_prelude::*,
+};
+use cargo_information::ops::{self, InfoOptions, SearchOptions, Section, Template};
+use cargo_util::paths;
+
+use crate::{completions, man};
+
+const OUTPUT_OPTIONS: &str = "Output Options";
 
 pub fn cli() -> Command {
     Command::new("cargo-info")
@@ -13,13 +24,176 @@ fn info_subcommand() -> Command {
         .about("Display info about a package in the registry")
         .arg(
             Arg::new("package")
-                .required(true)
+                .required_unless_present_any([
+                    "search",
+                    "compare",
+                    "generate-man",
+                    "completions",
+                    "complete-spec",
+                ])
                 .value_name("SPEC")
                 .help_heading(heading::PACKAGE_SELECTION)
-                .help("Package to inspect"),
+                .help("Package to inspect, or the path to a local package"),
+        )
+        .arg(
+            opt(
+                "search",
+                "Search the registry and show a card for each result",
+            )
+            .value_name("QUERY")
+            .conflicts_with("package")
+            .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg(
+            opt("pick", "Show the info of the N-th search result")
+                .value_name("N")
+                .value_parser(clap::value_parser!(usize))
+                .requires("search")
+                .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg(
+            flag(
+                "all-registries",
+                "Compare the package across crates.io and all configured registries",
+            )
+            .conflicts_with_all(["search", "index", "registry"])
+            .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg(
+            opt(
+                "compare",
+                "Compare the crates side by side, e.g. `ureq isahc`",
+            )
+            .value_name("SPEC")
+            .num_args(1..)
+            .conflicts_with_all(["package", "search", "all-registries"])
+            .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg(
+            opt("git", "Git URL to inspect the package from")
+                .value_name("URL")
+                .requires("package")
+                .conflicts_with_all(["search", "all-registries", "index", "registry"])
+                .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg(
+            opt("branch", "Branch to use when inspecting from git")
+                .value_name("BRANCH")
+                .requires("git")
+                .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg(
+            opt("tag", "Tag to use when inspecting from git")
+                .value_name("TAG")
+                .requires("git")
+                .conflicts_with("branch")
+                .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg(
+            opt("rev", "Specific commit to use when inspecting from git")
+                .value_name("REV")
+                .requires("git")
+                .conflicts_with_all(["branch", "tag"])
+                .help_heading(heading::PACKAGE_SELECTION),
+        )
+        .arg_features()
+        .arg(
+            opt(
+                "template",
+                "Render the package with a template, e.g. `{name}@{version}`",
+            )
+            .value_name("TEMPLATE")
+            .value_parser(|s: &str| s.parse::<Template>())
+            .conflicts_with_all(["template-file", "interactive", "all-registries", "compare"])
+            .help_heading(OUTPUT_OPTIONS),
+        )
+        .arg(
+            opt(
+                "template-file",
+                "Render the package with the template in the file",
+            )
+            .value_name("PATH")
+            .conflicts_with_all(["interactive", "all-registries", "compare"])
+            .help_heading(OUTPUT_OPTIONS),
         )
         .arg_index("Registry index URL to search packages in")
         .arg_registry("Registry to search packages in")
+        .arg(flag(
+            "follow-replacement",
+            "Query through the source replacing crates.io instead of failing",
+        ))
+        .arg(flag(
+            "transitive",
+            "Summarize the transitive dependencies of the package",
+        ))
+        .arg(
+            opt(
+                "advisory-db",
+                "Check the package against a local RustSec advisory database",
+            )
+            .value_name("PATH"),
+        )
+        .arg(flag(
+            "provenance",
+            "Verify the checksum of the package and show where it comes from",
+        ))
+        .arg(flag(
+            "stats",
+            "Show download statistics and publish history from the registry API",
+        ))
+        .arg(flag(
+            "reverse-deps",
+            "List the most downloaded dependents from the registry API",
+        ))
+        .arg(
+            opt("show", "Only show the given sections, e.g. `features,deps`")
+                .value_name("SECTIONS")
+                .value_parser(|s: &str| s.parse::<Section>())
+                .value_delimiter(',')
+                .action(ArgAction::Append),
+        )
+        .arg(
+            opt("hide", "Hide the given sections, e.g. `owners`")
+                .value_name("SECTIONS")
+                .value_parser(|s: &str| s.parse::<Section>())
+                .value_delimiter(',')
+                .action(ArgAction::Append),
+        )
+        .arg(
+            flag(
+                "interactive",
+                "Browse the package in an interactive terminal UI",
+            )
+            .conflicts_with_all(["search", "all-registries", "compare", "git"]),
+        )
+        .arg(flag("no-pager", "Do not pipe the output through a pager"))
+        .arg(
+            opt(
+                "generate-man",
+                "Write the man page and Markdown docs to the directory",
+            )
+            .value_name("DIR")
+            .value_parser(clap::value_parser!(PathBuf))
+            .hide(true),
+        )
+        .arg(
+            opt(
+                "completions",
+                "Print the completion script for bash, zsh or fish",
+            )
+            .value_name("SHELL")
+            .value_parser(completions::SHELLS)
+            .hide_possible_values(true),
+        )
+        .arg(
+            opt(
+                "complete-spec",
+                "Print the package specs starting with the prefix",
+            )
+            .value_name("PREFIX")
+            .conflicts_with("package")
+            .hide(true),
+        )
         .arg(
             opt(
                 "verbose",
@@ -74,7 +248,8 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     let color = args.get_one::<String>("color").cloned();
     let frozen = args.flag("frozen");
     let locked = args.flag("locked");
-    let offline = args.flag("offline");
+    // Completing must be fast, so only the local index cache is used.
+    let offline = args.flag("offline") || args.contains_id("complete-spec");
     let unstable_flags: Vec<String> = args
         .get_many::<String>("unstable-features")
         .unwrap_or_default()
@@ -97,10 +272,84 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
         &config_args,
     )?;
 
+    if let Some(dir) = args.get_one::<PathBuf>("generate-man") {
+        man::generate(info_subcommand(), dir, gctx)?;
+        return Ok(());
+    }
+    if let Some(shell) = args.get_one::<String>("completions") {
+        completions::generate(cli(), shell, gctx)?;
+        return Ok(());
+    }
+
+    let reg_or_index = args.registry_or_index(gctx)?;
+    if let Some(prefix) = args.get_one::<String>("complete-spec") {
+        ops::complete_spec(prefix, gctx, reg_or_index, args.flag("follow-replacement"))?;
+        return Ok(());
+    }
+    let template = match args.value_of_path("template-file", gctx) {
+        Some(path) => Some(
+            paths::read(&path)?
+                .parse::<Template>()
+                .with_context(|| format!("invalid template file `{}`", path.display()))?,
+        ),
+        None => args.get_one::<Template>("template").cloned(),
+    };
+    let opts = InfoOptions {
+        cli_features: args.cli_features()?,
+        transitive: args.flag("transitive"),
+        advisory_db: args.value_of_path("advisory-db", gctx),
+        stats: args.flag("stats"),
+        reverse_deps: args.flag("reverse-deps"),
+        follow_replacement: args.flag("follow-replacement"),
+        provenance: args.flag("provenance"),
+        show: args
+            .get_many::<Section>("show")
+            .map(|sections| sections.copied().collect()),
+        hide: args
+            .get_many::<Section>("hide")
+            .unwrap_or_default()
+            .copied()
+            .collect(),
+        pager: !args.flag("no-pager"),
+        template,
+    };
+
+    if let Some(query) = args.get_one::<String>("search") {
+        let opts = SearchOptions {
+            pick: args.get_one::<usize>("pick").copied(),
+            info: opts,
+        };
+        ops::search(query, gctx, reg_or_index, &opts)?;
+        return Ok(());
+    }
+
+    if let Some(packages) = args.get_many::<String>("compare") {
+        let specs = packages
+            .map(|package| {
+                PackageIdSpec::parse(package).map_err(|e| {
+                    anyhow::format_err!(
+                        "invalid package id specification `{}`: {}",
+                        package,
+                        e.to_string()
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        ops::compare_crates(&specs, gctx, reg_or_index, &opts)?;
+        return Ok(());
+    }
+
     let package = args
         .get_one::<String>("package")
         .map(String::as_str)
         .unwrap();
+    if ops::is_package_path(package) && !args.contains_id("git") {
+        if args.flag("interactive") {
+            anyhow::bail!("`--interactive` is not supported for local packages");
+        }
+        ops::info_path(Path::new(package), gctx, &opts)?;
+        return Ok(());
+    }
     let spec = PackageIdSpec::parse(package).map_err(|e| {
         anyhow::format_err!(
             "invalid package id specification `{}`: {}",
@@ -108,9 +357,28 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
             e.to_string()
         )
     })?;
-
-    let reg_or_index = args.registry_or_index(gctx)?;
-    ops::info(&spec, gctx, reg_or_index)?;
+    if let Some(url) = args.get_one::<String>("git") {
+        let reference = if let Some(branch) = args.get_one::<String>("branch") {
+            GitReference::Branch(branch.clone())
+        } else if let Some(tag) = args.get_one::<String>("tag") {
+            GitReference::Tag(tag.clone())
+        } else if let Some(rev) = args.get_one::<String>("rev") {
+            GitReference::Rev(rev.clone())
+        } else {
+            GitReference::DefaultBranch
+        };
+        ops::info_git(&spec, url, reference, gctx, &opts)?;
+        return Ok(());
+    }
+    if args.flag("all-registries") {
+        ops::compare_registries(&spec, gctx)?;
+        return Ok(());
+    }
+    if args.flag("interactive") {
+        ops::interactive(&spec, gctx, reg_or_index, &opts)?;
+        return Ok(());
+    }
+    ops::info(&spec, gctx, reg_or_index, &opts)?;
     Ok(())
 }
 
@@ -0,0 +1,137 @@
+use std::io::Write as _;
+
+use cargo::{CargoResult, GlobalContext};
+use clap::Command;
+use clap_complete::Shell;
+
+/// The shells that completion scripts are generated for.
+pub const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+/// The binary the bash and zsh completions are generated for, so that they don't define the
+/// `_cargo` function of the completion of Cargo itself.
+const GENERATED_BIN: &str = "cargo-info";
+
+// Print the completion script of `cargo info` for the shell. The flags come from the CLI
+// definition, and the package spec is completed by calling back `cargo info --complete-spec`.
+//
+// Only `cargo info` is completed, the other subcommands are left to the completion registered
+// for `cargo` before, usually by rustup.
+pub fn generate(mut cmd: Command, shell: &str, gctx: &GlobalContext) -> CargoResult<()> {
+    let shell = shell.parse::<Shell>().map_err(anyhow::Error::msg)?;
+    let mut script = Vec::new();
+    // Fish completions add up, so they can be registered for `cargo` directly.
+    let bin = match shell {
+        Shell::Fish => "cargo",
+        _ => GENERATED_BIN,
+    };
+    clap_complete::generate(shell, &mut cmd, bin, &mut script);
+
+    // The spec completion must not kick in for the values of options, except for the specs
+    // given to `--compare`.
+    let info = cmd.find_subcommand("info").unwrap();
+    let mut value_options = Vec::new();
+    for arg in info.get_arguments() {
+        if arg.is_positional() || !arg.get_action().takes_values() || arg.get_id() == "compare" {
+            continue;
+        }
+        value_options.extend(arg.get_short().map(|short| format!("-{short}")));
+        value_options.extend(arg.get_long().map(|long| format!("--{long}")));
+    }
+    let spec_script = match shell {
+        Shell::Bash => BASH.replace("{options}", &value_options.join("|")),
+        Shell::Zsh => ZSH.replace("{options}", &value_options.join("|")),
+        Shell::Fish => FISH.to_string(),
+        _ => unreachable!("`--completions` only accepts {SHELLS:?}"),
+    };
+    script.extend_from_slice(spec_script.as_bytes());
+
+    gctx.shell().out().write_all(&script)?;
+    Ok(())
+}
+
+const BASH: &str = r#"
+# Keep the completion registered for cargo before, usually by rustup, for the other subcommands.
+if ! complete -p cargo &>/dev/null && declare -F _completion_loader &>/dev/null; then
+    _completion_loader cargo
+fi
+if [[ "$(complete -p cargo 2>/dev/null)" =~ -F\ ([^ ]+) && "${BASH_REMATCH[1]}" != _cargo_info ]]; then
+    _cargo_info_fallback="${BASH_REMATCH[1]}"
+fi
+
+# Complete the package spec of `cargo info` from the workspace and the local index cache.
+_cargo_info() {
+    # `@` breaks words in bash, so the words are split again from the command line.
+    local line="${COMP_LINE:0:COMP_POINT}" cur="" prev
+    local -a words
+    read -ra words <<< "${line}"
+    if [[ "${line}" == *[[:space:]] ]]; then
+        prev="${words[${#words[@]}-1]}"
+    else
+        cur="${words[${#words[@]}-1]}"
+        prev="${words[${#words[@]}-2]}"
+    fi
+    if [[ "${words[1]}" != "info" || "${prev}" == "cargo" ]]; then
+        if [[ -n "${_cargo_info_fallback}" ]]; then
+            "${_cargo_info_fallback}" "$@"
+        fi
+        return
+    fi
+    if [[ "${cur}" != -* ]]; then
+        case "${prev}" in
+            {options})
+                ;;
+            *)
+                COMPREPLY=( $(compgen -W "$(cargo info --complete-spec "${cur}" 2>/dev/null)" -- "${cur}") )
+                if [[ ${#COMPREPLY[@]} -gt 0 ]]; then
+                    if [[ "${cur}" == *@* && "${COMP_WORDBREAKS}" == *@* ]]; then
+                        COMPREPLY=( "${COMPREPLY[@]#"${cur%"${cur##*@}"}"}" )
+                    fi
+                    return 0
+                fi
+                ;;
+        esac
+    fi
+    _cargo__info "$@"
+}
+
+complete -F _cargo_info -o bashdefault -o default cargo
+"#;
+
+const ZSH: &str = r#"
+# Keep the completion registered for cargo before, usually by rustup, for the other subcommands.
+if [[ ${_comps[cargo]} != _cargo_info ]]; then
+    _cargo_info_fallback=${_comps[cargo]}
+fi
+
+# Complete the package spec of `cargo info` from the workspace and the local index cache.
+_cargo_info() {
+    if [[ ${words[2]} != info || $CURRENT -le 2 ]]; then
+        if [[ -n $_cargo_info_fallback ]]; then
+            $_cargo_info_fallback "$@"
+        fi
+        return
+    fi
+    if [[ $PREFIX != -* ]]; then
+        case ${words[CURRENT-1]} in
+            ({options})
+                ;;
+            (*)
+                local -a specs
+                specs=(${(f)"$(cargo info --complete-spec "$PREFIX" 2>/dev/null)"})
+                if (( ${#specs} )); then
+                    compadd -a specs
+                    return
+                fi
+                ;;
+        esac
+    fi
+    _cargo-info "$@"
+}
+
+compdef _cargo_info cargo
+"#;
+
+const FISH: &str = r#"
+# Complete the package spec of `cargo info` from the workspace and the local index cache.
+complete -c cargo -n "__fish_seen_subcommand_from info" -a "(cargo info --complete-spec (commandline -ct) 2>/dev/null)"
+"#;
@@ -2,6 +2,8 @@ use cargo::core::Shell;
 
 mod cli;
 mod command;
+mod completions;
+mod man;
 
 fn main() {
     let mut ctx = match cargo::GlobalContext::default() {
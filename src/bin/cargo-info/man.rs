@@ -0,0 +1,265 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use cargo::{CargoResult, GlobalContext};
+use cargo_util::paths;
+use clap::{Arg, Command};
+use clap_mangen::roff::{bold, roman, Roff};
+use clap_mangen::Man;
+
+/// A block of the hand-written sections, rendered to both roff and Markdown.
+/// Text between backticks is code in Markdown, the backticks are dropped in roff.
+enum Block {
+    Paragraph(&'static str),
+    /// A term and its description, consecutive items make a list.
+    Item(&'static str, &'static str),
+    /// A description and the command it describes.
+    Example(&'static str, &'static str),
+}
+
+const DESCRIPTION: &[Block] = &[
+    Block::Paragraph(
+        "Display information about a package: its description, version, license, links, \
+         features and dependencies. More sections are available on demand, like the owners, \
+         the download statistics, the transitive dependencies or the known advisories.",
+    ),
+    Block::Paragraph(
+        "The package is looked up in the current workspace first, then in the registry, \
+         see VERSION SELECTION.",
+    ),
+];
+
+const SECTIONS: &[(&str, &[Block])] = &[
+    (
+        "SPEC SYNTAX",
+        &[
+            Block::Item("name", "The package named `name`, e.g. `serde`."),
+            Block::Item(
+                "name@version",
+                "The package with a version matching `version`, which can be partial, \
+                 e.g. `serde@1` or `serde@1.0.197`.",
+            ),
+            Block::Item(
+                "path",
+                "The package at a local path, e.g. `.` or `crates/foo`. A spec starting \
+                 with `.` or containing a path separator is a path.",
+            ),
+        ],
+    ),
+    (
+        "VERSION SELECTION",
+        &[
+            Block::Paragraph(
+                "When the spec doesn't pin a version, the version is selected in this order:",
+            ),
+            Block::Item(
+                "workspace",
+                "A workspace member matching the spec is shown as it is on disk.",
+            ),
+            Block::Item(
+                "lockfile",
+                "Otherwise, the version in `Cargo.lock` is preferred, first among the \
+                 dependencies of the nearest package, then of the other members, then \
+                 anywhere in the lockfile.",
+            ),
+            Block::Item(
+                "rust-version",
+                "Otherwise, the latest version compatible with the `rust-version` of the \
+                 workspace, or with the current `rustc` outside a workspace, is picked \
+                 from the registry, falling back to the latest version.",
+            ),
+        ],
+    ),
+    (
+        "EXAMPLES",
+        &[
+            Block::Example("Show the information about a package:", "cargo info serde"),
+            Block::Example(
+                "Show a specific version, with its optional features activated:",
+                "cargo info serde@1.0.197 --features derive",
+            ),
+            Block::Example(
+                "Show only the features and dependencies, with all the dependencies listed:",
+                "cargo info tokio --show features,deps --verbose",
+            ),
+            Block::Example(
+                "Show a package from an alternative registry:",
+                "cargo info my-package --registry my-registry",
+            ),
+            Block::Example("Show the package in the current directory:", "cargo info ."),
+            Block::Example(
+                "Compare alternative crates side by side:",
+                "cargo info --compare reqwest ureq isahc",
+            ),
+        ],
+    ),
+];
+
+// Write the `cargo-info(1)` man page and its Markdown version to the directory.
+pub fn generate(cmd: Command, dir: &Path, gctx: &GlobalContext) -> CargoResult<()> {
+    let mut cmd = cmd.display_name("cargo-info").bin_name("cargo info");
+    cmd.build();
+
+    paths::create_dir_all(dir)?;
+    for (name, contents) in [
+        ("cargo-info.1", render_man(&cmd)?),
+        ("cargo-info.md", render_markdown(&cmd)),
+    ] {
+        let path = dir.join(name);
+        paths::write(&path, contents)?;
+        gctx.shell().status("Generated", path.display())?;
+    }
+    Ok(())
+}
+
+fn render_man(cmd: &Command) -> CargoResult<String> {
+    let man = Man::new(cmd.clone())
+        .section("1")
+        .manual("General Commands Manual");
+    let mut buffer = Vec::new();
+    man.render_title(&mut buffer)?;
+    man.render_name_section(&mut buffer)?;
+    man.render_synopsis_section(&mut buffer)?;
+    render_man_section(&mut buffer, "DESCRIPTION", DESCRIPTION)?;
+    man.render_options_section(&mut buffer)?;
+    for (title, blocks) in SECTIONS {
+        render_man_section(&mut buffer, title, blocks)?;
+    }
+    Ok(String::from_utf8(buffer)?)
+}
+
+fn render_man_section(buffer: &mut Vec<u8>, title: &str, blocks: &[Block]) -> std::io::Result<()> {
+    let mut roff = Roff::new();
+    roff.control("SH", [title]);
+    for block in blocks {
+        match block {
+            Block::Paragraph(text) => {
+                roff.control("PP", []);
+                roff.text([roman(strip_code(text))]);
+            }
+            Block::Item(term, description) => {
+                roff.control("TP", []);
+                roff.text([bold(*term)]);
+                roff.text([roman(strip_code(description))]);
+            }
+            Block::Example(description, command) => {
+                roff.control("PP", []);
+                roff.text([roman(strip_code(description))]);
+                roff.control("RS", ["4"]);
+                roff.control("nf", []);
+                roff.text([roman(*command)]);
+                roff.control("fi", []);
+                roff.control("RE", []);
+            }
+        }
+    }
+    roff.to_writer(buffer)
+}
+
+fn render_markdown(cmd: &Command) -> String {
+    let mut markdown = String::from("# cargo-info(1)\n");
+
+    markdown.push_str("\n## NAME\n\n");
+    let about = cmd.get_about().map(|about| about.to_string());
+    writeln!(markdown, "cargo-info - {}", about.unwrap_or_default()).unwrap();
+
+    markdown.push_str("\n## SYNOPSIS\n\n");
+    let mut synopsis = format!("{} [OPTIONS]", cmd.get_bin_name().unwrap_or_default());
+    for arg in cmd.get_positionals().filter(|arg| !arg.is_hide_set()) {
+        write!(synopsis, " {}", arg_usage(arg)).unwrap();
+    }
+    writeln!(markdown, "`{synopsis}`").unwrap();
+
+    markdown.push_str("\n## DESCRIPTION\n");
+    render_markdown_blocks(&mut markdown, DESCRIPTION);
+
+    // The options without a heading come first, like in `--help`.
+    markdown.push_str("\n## OPTIONS\n");
+    let mut headings: Vec<Option<&str>> = vec![None];
+    for arg in cmd.get_arguments() {
+        if !headings.contains(&arg.get_help_heading()) {
+            headings.push(arg.get_help_heading());
+        }
+    }
+    for heading in headings {
+        if let Some(heading) = heading {
+            writeln!(markdown, "\n### {heading}").unwrap();
+        }
+        markdown.push('\n');
+        for arg in cmd
+            .get_arguments()
+            .filter(|arg| arg.get_help_heading() == heading && !arg.is_hide_set())
+        {
+            let help = arg.get_help().map(|help| help.to_string());
+            writeln!(
+                markdown,
+                "- `{}`: {}",
+                arg_usage(arg),
+                help.unwrap_or_default()
+            )
+            .unwrap();
+        }
+    }
+
+    for (title, blocks) in SECTIONS {
+        writeln!(markdown, "\n## {title}").unwrap();
+        render_markdown_blocks(&mut markdown, blocks);
+    }
+    markdown
+}
+
+fn render_markdown_blocks(markdown: &mut String, blocks: &[Block]) {
+    let mut in_list = false;
+    for block in blocks {
+        match block {
+            Block::Paragraph(text) => {
+                writeln!(markdown, "\n{text}").unwrap();
+                in_list = false;
+            }
+            Block::Item(term, description) => {
+                if !in_list {
+                    markdown.push('\n');
+                }
+                writeln!(markdown, "- `{term}`: {description}").unwrap();
+                in_list = true;
+            }
+            Block::Example(description, command) => {
+                writeln!(markdown, "\n{description}\n\n```\n{command}\n```").unwrap();
+                in_list = false;
+            }
+        }
+    }
+}
+
+// Render the argument like in `--help`, e.g. `-F, --features <FEATURES>` or `[SPEC]`.
+fn arg_usage(arg: &Arg) -> String {
+    let value_names = match arg.get_value_names() {
+        Some(names) => names.iter().map(|name| name.to_string()).collect(),
+        None => vec![arg.get_id().as_str().to_uppercase()],
+    };
+    if arg.is_positional() {
+        let names = value_names.join(" ");
+        return if arg.is_required_set() {
+            format!("<{names}>")
+        } else {
+            format!("[{names}]")
+        };
+    }
+
+    let mut usage = match (arg.get_short(), arg.get_long()) {
+        (Some(short), Some(long)) => format!("-{short}, --{long}"),
+        (Some(short), None) => format!("-{short}"),
+        (None, Some(long)) => format!("--{long}"),
+        (None, None) => unreachable!("options have a short or long name"),
+    };
+    if arg.get_action().takes_values() {
+        for name in value_names {
+            write!(usage, " <{name}>").unwrap();
+        }
+    }
+    usage
+}
+
+fn strip_code(text: &str) -> String {
+    text.replace('`', "")
+}
@@ -0,0 +1,259 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context as _;
+use cargo::core::PackageId;
+use cargo::sources::IndexSummary;
+use cargo::CargoResult;
+use cargo_util_schemas::core::PartialVersion;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+
+/// Advisories from a RustSec advisory database that affect a package.
+pub(super) struct AdvisoryReport {
+    /// Advisories affecting the selected version, sorted by ID.
+    pub(super) advisories: Vec<Advisory>,
+    /// The nearest newer version that is not affected by any of the advisories.
+    pub(super) recommended: Option<PackageId>,
+}
+
+pub(super) struct Advisory {
+    pub(super) id: String,
+    pub(super) title: String,
+    /// Qualitative severity rating computed from the CVSS vector.
+    pub(super) severity: Option<&'static str>,
+    /// The kind of informational advisory, e.g. `unmaintained` or `unsound`.
+    pub(super) informational: Option<String>,
+    /// Version requirements of the patched versions, as written in the advisory.
+    pub(super) patched: Vec<String>,
+    patched_reqs: Vec<VersionReq>,
+    unaffected_reqs: Vec<VersionReq>,
+}
+
+impl Advisory {
+    fn affects(&self, version: &Version) -> bool {
+        !self
+            .patched_reqs
+            .iter()
+            .chain(self.unaffected_reqs.iter())
+            .any(|req| req.matches(version))
+    }
+}
+
+#[derive(Deserialize)]
+struct AdvisoryFile {
+    advisory: AdvisoryMetadata,
+    #[serde(default)]
+    versions: AdvisoryVersions,
+}
+
+#[derive(Deserialize)]
+struct AdvisoryMetadata {
+    id: String,
+    package: String,
+    /// Only present in the legacy TOML format, the Markdown format uses the first heading.
+    title: Option<String>,
+    cvss: Option<String>,
+    informational: Option<String>,
+    withdrawn: Option<toml::Value>,
+}
+
+#[derive(Default, Deserialize)]
+struct AdvisoryVersions {
+    #[serde(default)]
+    patched: Vec<String>,
+    #[serde(default)]
+    unaffected: Vec<String>,
+}
+
+// Check the selected package against a local checkout of the RustSec advisory database.
+pub(super) fn check(
+    db: &Path,
+    package_id: PackageId,
+    summaries: &[IndexSummary],
+    rustc_version: &PartialVersion,
+) -> CargoResult<AdvisoryReport> {
+    if !db.is_dir() {
+        anyhow::bail!("advisory database `{}` does not exist", db.display());
+    }
+
+    let mut advisories = load_advisories(db, package_id.name().as_str())?
+        .into_iter()
+        .filter(|advisory| advisory.affects(package_id.version()))
+        .collect::<Vec<_>>();
+    advisories.sort_by(|a1, a2| a1.id.cmp(&a2.id));
+
+    let recommended = if advisories.is_empty() {
+        None
+    } else {
+        // Pick the lowest unaffected version newer than the selected one, and prefer the
+        // MSRV compatible ones.
+        summaries
+            .iter()
+            .filter(|s| !s.is_yanked())
+            .map(|s| s.as_summary())
+            .filter(|s| s.version() > package_id.version())
+            .filter(|s| advisories.iter().all(|a| !a.affects(s.version())))
+            .min_by_key(|s| {
+                let is_compatible = s
+                    .rust_version()
+                    .map(|v| v.is_compatible_with(rustc_version))
+                    .unwrap_or(false);
+                (!is_compatible, s.version().clone())
+            })
+            .map(|s| s.package_id())
+    };
+
+    Ok(AdvisoryReport {
+        advisories,
+        recommended,
+    })
+}
+
+fn load_advisories(db: &Path, package_name: &str) -> CargoResult<Vec<Advisory>> {
+    let dir = db.join("crates").join(package_name);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut advisories = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if !matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("md") | Some("toml")
+        ) {
+            continue;
+        }
+        let advisory = parse_advisory(&path, package_name)
+            .with_context(|| format!("failed to parse advisory `{}`", path.display()))?;
+        advisories.extend(advisory);
+    }
+    Ok(advisories)
+}
+
+// Parse an advisory in either the Markdown format, where the metadata is a TOML code block
+// followed by the title heading and the description, or the legacy TOML format.
+// Withdrawn advisories and advisories for other packages are skipped.
+fn parse_advisory(path: &Path, package_name: &str) -> CargoResult<Option<Advisory>> {
+    let content = fs::read_to_string(path)?;
+    let (front_matter, markdown) = match content.strip_prefix("```toml") {
+        Some(rest) => {
+            let (front_matter, markdown) = rest
+                .split_once("\n```")
+                .context("missing the end of the TOML front matter")?;
+            (front_matter, Some(markdown))
+        }
+        None => (content.as_str(), None),
+    };
+    let file: AdvisoryFile = toml::from_str(front_matter)?;
+    if file.advisory.withdrawn.is_some() || file.advisory.package != package_name {
+        return Ok(None);
+    }
+
+    let title = markdown
+        .and_then(|markdown| {
+            markdown
+                .lines()
+                .find_map(|line| line.strip_prefix("# "))
+                .map(|title| title.trim().to_string())
+        })
+        .or(file.advisory.title)
+        .unwrap_or_default();
+    let parse_reqs = |reqs: &[String]| {
+        reqs.iter()
+            .map(|req| {
+                VersionReq::parse(req)
+                    .with_context(|| format!("invalid version requirement `{req}`"))
+            })
+            .collect::<CargoResult<Vec<_>>>()
+    };
+
+    Ok(Some(Advisory {
+        id: file.advisory.id,
+        title,
+        severity: file.advisory.cvss.as_deref().and_then(cvss_severity),
+        informational: file.advisory.informational,
+        patched_reqs: parse_reqs(&file.versions.patched)?,
+        unaffected_reqs: parse_reqs(&file.versions.unaffected)?,
+        patched: file.versions.patched,
+    }))
+}
+
+// Compute the qualitative severity rating of a CVSS v3 vector from its base score.
+// See https://www.first.org/cvss/v3.1/specification-document.
+fn cvss_severity(vector: &str) -> Option<&'static str> {
+    let mut metrics = vector.split('/');
+    if !metrics.next()?.starts_with("CVSS:3") {
+        return None;
+    }
+    let metrics = metrics
+        .filter_map(|m| m.split_once(':'))
+        .collect::<Vec<_>>();
+    let metric = |name: &str| {
+        metrics
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| *value)
+    };
+
+    let scope_changed = metric("S")? == "C";
+    let attack_vector = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let attack_complexity = match metric("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let privileges_required = match (metric("PR")?, scope_changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let user_interaction = match metric("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact = |name: &str| match metric(name)? {
+        "H" => Some(0.56),
+        "L" => Some(0.22),
+        "N" => Some(0.0),
+        _ => None,
+    };
+    let iss: f64 = 1.0 - (1.0 - impact("C")?) * (1.0 - impact("I")?) * (1.0 - impact("A")?);
+
+    let impact = if scope_changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15)
+    } else {
+        6.42 * iss
+    };
+    let exploitability =
+        8.22 * attack_vector * attack_complexity * privileges_required * user_interaction;
+    let score = if impact <= 0.0 {
+        0.0
+    } else if scope_changed {
+        (1.08 * (impact + exploitability)).min(10.0)
+    } else {
+        (impact + exploitability).min(10.0)
+    };
+    // Round up to one decimal place.
+    let score = (score * 10.0).ceil() / 10.0;
+
+    let severity = match score {
+        s if s <= 0.0 => "none",
+        s if s < 4.0 => "low",
+        s if s < 7.0 => "medium",
+        s if s < 9.0 => "high",
+        _ => "critical",
+    };
+    Some(severity)
+}
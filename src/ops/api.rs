@@ -0,0 +1,219 @@
+use anyhow::Context as _;
+use cargo::CargoResult;
+use curl::easy::{Easy, List};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use super::cache::ApiCache;
+
+/// A client for the read-only endpoints of the registry web API that are not covered
+/// by the `crates-io` crate. Responses are cached on disk.
+pub(super) struct ApiClient<'gctx> {
+    host: String,
+    token: Option<String>,
+    /// `None` in offline mode, only cached responses are available then.
+    handle: Option<Easy>,
+    cache: ApiCache<'gctx>,
+}
+
+impl<'gctx> ApiClient<'gctx> {
+    pub(super) fn new(
+        host: String,
+        token: Option<String>,
+        handle: Option<Easy>,
+        cache: ApiCache<'gctx>,
+    ) -> ApiClient<'gctx> {
+        ApiClient {
+            host,
+            token,
+            handle,
+            cache,
+        }
+    }
+
+    // Get the crate metadata and the versions of a crate.
+    pub(super) fn crate_stats(&mut self, name: &str) -> CargoResult<CrateStats> {
+        self.get(&format!("/crates/{name}"))
+    }
+
+    // Get the most downloaded dependents of a crate, up to `limit`.
+    pub(super) fn reverse_dependencies(
+        &mut self,
+        name: &str,
+        limit: usize,
+    ) -> CargoResult<ReverseDependencies> {
+        // The maximum page size supported by crates.io.
+        const PER_PAGE: usize = 100;
+
+        let mut dependents = Vec::new();
+        let mut total = 0;
+        let mut fetched = 0;
+        for page in 1.. {
+            let response: ReverseDependenciesPage = self.get(&format!(
+                "/crates/{name}/reverse_dependencies?page={page}&per_page={}",
+                PER_PAGE.min(limit)
+            ))?;
+            total = response.meta.total;
+            fetched += response.dependencies.len();
+            let is_last_page = response.dependencies.is_empty();
+            dependents.extend(response.dependencies.into_iter().filter_map(|dep| {
+                let version = response.versions.iter().find(|v| v.id == dep.version_id)?;
+                Some(Dependent {
+                    name: version.krate.clone(),
+                    version: version.num.clone(),
+                    req: dep.req,
+                    optional: dep.optional,
+                    kind: dep.kind,
+                    downloads: dep.downloads,
+                })
+            }));
+            if is_last_page || dependents.len() >= limit || fetched as u64 >= total {
+                break;
+            }
+        }
+        dependents.sort_by(|d1, d2| d2.downloads.cmp(&d1.downloads));
+        dependents.truncate(limit);
+
+        Ok(ReverseDependencies { total, dependents })
+    }
+
+    fn get<T: DeserializeOwned>(&mut self, path: &str) -> CargoResult<T> {
+        if let Some(response) = self.cache.get(path)? {
+            return Ok(response);
+        }
+        let url = format!("{}/api/v1{}", self.host.trim_end_matches('/'), path);
+        let Some(handle) = self.handle.as_mut() else {
+            anyhow::bail!("`{url}` is not cached, cannot query the registry API in offline mode");
+        };
+        handle.get(true)?;
+        handle.url(&url)?;
+        let mut headers = List::new();
+        headers.append("Accept: application/json")?;
+        if let Some(token) = &self.token {
+            headers.append(&format!("Authorization: {token}"))?;
+        }
+        handle.http_headers(headers)?;
+
+        let mut body = Vec::new();
+        {
+            let mut handle = handle.transfer();
+            handle.write_function(|buf| {
+                body.extend_from_slice(buf);
+                Ok(buf.len())
+            })?;
+            handle
+                .perform()
+                .with_context(|| format!("failed to get `{url}`"))?;
+        }
+
+        let code = handle.response_code()?;
+        if code != 200 {
+            let errors = serde_json::from_slice::<ApiErrorList>(&body)
+                .map(|e| {
+                    e.errors
+                        .into_iter()
+                        .map(|e| e.detail)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+            anyhow::bail!("failed to get `{url}` (status {code}): {errors}");
+        }
+        // Cache the raw response, so that it can be read back as any type.
+        let response: serde_json::Value = serde_json::from_slice(&body)
+            .with_context(|| format!("invalid response from `{url}`"))?;
+        self.cache.put(path, &response);
+        serde_json::from_value(response).with_context(|| format!("invalid response from `{url}`"))
+    }
+}
+
+#[derive(Deserialize)]
+struct ApiErrorList {
+    errors: Vec<ApiError>,
+}
+
+#[derive(Deserialize)]
+struct ApiError {
+    detail: String,
+}
+
+/// Download statistics and publish history of a crate.
+#[derive(Deserialize)]
+pub(super) struct CrateStats {
+    #[serde(rename = "crate")]
+    pub(super) krate: CrateData,
+    #[serde(default)]
+    pub(super) versions: Option<Vec<VersionData>>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct CrateData {
+    #[serde(default)]
+    pub(super) keywords: Vec<String>,
+    pub(super) downloads: u64,
+    pub(super) recent_downloads: Option<u64>,
+    pub(super) created_at: String,
+    pub(super) updated_at: String,
+}
+
+#[derive(Deserialize)]
+pub(super) struct VersionData {
+    pub(super) num: String,
+    pub(super) created_at: String,
+    #[serde(default)]
+    pub(super) yanked: bool,
+    pub(super) published_by: Option<Publisher>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct Publisher {
+    pub(super) login: String,
+    pub(super) name: Option<String>,
+}
+
+/// The crates depending on a crate, most downloaded first.
+pub(super) struct ReverseDependencies {
+    /// The total number of dependents, including the ones not fetched.
+    pub(super) total: u64,
+    pub(super) dependents: Vec<Dependent>,
+}
+
+pub(super) struct Dependent {
+    pub(super) name: String,
+    pub(super) version: String,
+    pub(super) req: String,
+    pub(super) optional: bool,
+    pub(super) kind: Option<String>,
+    pub(super) downloads: u64,
+}
+
+#[derive(Deserialize)]
+struct ReverseDependenciesPage {
+    dependencies: Vec<ReverseDependency>,
+    versions: Vec<DependentVersion>,
+    meta: Meta,
+}
+
+#[derive(Deserialize)]
+struct ReverseDependency {
+    version_id: u64,
+    req: String,
+    #[serde(default)]
+    optional: bool,
+    kind: Option<String>,
+    #[serde(default)]
+    downloads: u64,
+}
+
+#[derive(Deserialize)]
+struct DependentVersion {
+    id: u64,
+    #[serde(rename = "crate")]
+    krate: String,
+    num: String,
+}
+
+#[derive(Deserialize)]
+struct Meta {
+    total: u64,
+}
@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cargo::core::SourceId;
+use cargo::util::short_hash;
+use cargo::{CargoResult, GlobalContext};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use super::config::InfoConfig;
+use super::date;
+use super::info::registry_dir_name;
+
+/// How long a cached response is used before querying the registry API again, in seconds.
+const DEFAULT_TTL: u64 = 60 * 60;
+
+/// An on-disk cache of registry API responses, under `$CARGO_HOME/info-cache`.
+///
+/// Fresh responses are used instead of querying the registry API. In offline mode, cached
+/// responses are used regardless of their age, always with a note telling when they were fetched.
+pub(super) struct ApiCache<'gctx> {
+    gctx: &'gctx GlobalContext,
+    dir: PathBuf,
+    /// The maximum age of a response before it is stale, in seconds.
+    ttl: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// Seconds since the Unix epoch when the response was fetched.
+    fetched_at: u64,
+    response: serde_json::Value,
+}
+
+impl<'gctx> ApiCache<'gctx> {
+    pub(super) fn new(gctx: &'gctx GlobalContext, source_id: SourceId) -> CargoResult<Self> {
+        // Keep one directory per registry, named like its index directory.
+        let dir = gctx
+            .home()
+            .join("info-cache")
+            .join(registry_dir_name(source_id))
+            .into_path_unlocked();
+        let ttl = InfoConfig::load(gctx)?.cache_ttl.unwrap_or(DEFAULT_TTL);
+        Ok(ApiCache { gctx, dir, ttl })
+    }
+
+    // Get the cached response for the key if it is fresh, or of any age in offline mode.
+    // Offline, the response can't be checked against the registry, so its date is noted.
+    pub(super) fn get<T: DeserializeOwned>(&self, key: &str) -> CargoResult<Option<T>> {
+        let Ok(content) = fs::read(self.path(key)) else {
+            return Ok(None);
+        };
+        // A corrupted or outdated entry is the same as a missing one, it will be overwritten.
+        let Ok(entry) = serde_json::from_slice::<CacheEntry>(&content) else {
+            return Ok(None);
+        };
+        let Ok(response) = serde_json::from_value(entry.response) else {
+            return Ok(None);
+        };
+        if !self.gctx.offline() {
+            let is_fresh = now().saturating_sub(entry.fetched_at) < self.ttl;
+            return Ok(is_fresh.then_some(response));
+        }
+        let fetched_at = i64::try_from(entry.fetched_at).unwrap_or(i64::MAX);
+        self.gctx.shell().note(format!(
+            "showing `{key}` from the registry API as of {}",
+            date::format_timestamp(fetched_at)
+        ))?;
+        Ok(Some(response))
+    }
+
+    // Cache the response for the key.
+    // The cache is only an optimization, so failing to write it is not an error.
+    pub(super) fn put<T: Serialize>(&self, key: &str, response: &T) {
+        let Ok(response) = serde_json::to_value(response) else {
+            return;
+        };
+        let entry = CacheEntry {
+            fetched_at: now(),
+            response,
+        };
+        let Ok(content) = serde_json::to_vec(&entry) else {
+            return;
+        };
+        let _ = fs::create_dir_all(&self.dir).and_then(|()| fs::write(self.path(key), content));
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", short_hash(key)))
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
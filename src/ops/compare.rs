@@ -0,0 +1,168 @@
+use std::collections::HashSet;
+
+use cargo::core::dependency::DepKind;
+use cargo::core::resolver::CliFeatures;
+use cargo::core::{PackageIdSpec, Workspace};
+use cargo::ops::RegistryOrIndex;
+use cargo::util::command_prelude::root_manifest;
+use cargo::util::interning::InternedString;
+use cargo::{CargoResult, GlobalContext};
+use cargo_util_schemas::core::PartialVersion;
+use semver::Version;
+
+use super::info::{get_rustc_version, package_info, InfoOptions, PackageInfo};
+use super::pager;
+use super::sections::Section;
+use super::view::{dependency_status, pretty_comparison, pretty_date, resolve_features};
+
+/// The crates compared side by side, in the order they were given.
+pub(super) struct CrateComparison {
+    pub(super) crates: Vec<ComparedCrate>,
+    /// The workspace MSRV or the current Rust version, checked against each `rust-version`.
+    pub(super) rustc_version: PartialVersion,
+    /// Whether the publish dates were requested from the registry API.
+    pub(super) stats: bool,
+}
+
+/// What is compared of a crate, for the version selected like `cargo info` does.
+pub(super) struct ComparedCrate {
+    pub(super) name: String,
+    pub(super) version: Version,
+    /// The latest version that is not yanked.
+    pub(super) latest: Option<Version>,
+    pub(super) rust_version: Option<String>,
+    /// Whether the `rust-version` is compatible with the workspace MSRV or the current Rust version.
+    pub(super) is_msrv_compatible: bool,
+    pub(super) license: Option<String>,
+    /// The number of features enabled by `default`, excluding `default` itself.
+    pub(super) default_features: usize,
+    /// The normal and build dependencies enabled with the default features.
+    pub(super) direct_dependencies: usize,
+    pub(super) transitive_dependencies: usize,
+    pub(super) build_script: bool,
+    pub(super) proc_macro: bool,
+    /// The number of transitive dependencies with a build script.
+    pub(super) transitive_build_scripts: usize,
+    /// The number of transitive dependencies that are procedural macros.
+    pub(super) transitive_proc_macros: usize,
+    /// The number of transitive dependencies not downloaded yet, which are not inspected for
+    /// build scripts and procedural macros.
+    pub(super) transitive_not_downloaded: usize,
+    pub(super) owners: Option<usize>,
+    /// The date of the most recent publish, from the registry API.
+    pub(super) last_publish: Option<String>,
+}
+
+// Gather each crate like `cargo info` does, with its default features and transitive
+// dependencies, and compare them in a table.
+pub fn compare_crates(
+    specs: &[PackageIdSpec],
+    gctx: &GlobalContext,
+    reg_or_index: Option<RegistryOrIndex>,
+    opts: &InfoOptions,
+) -> CargoResult<()> {
+    // Only gather what is compared, the other sections would query the registry for nothing.
+    let compare_opts = InfoOptions {
+        cli_features: CliFeatures::new_all(false),
+        transitive: true,
+        advisory_db: None,
+        stats: opts.stats,
+        reverse_deps: false,
+        follow_replacement: opts.follow_replacement,
+        provenance: false,
+        show: Some(
+            [Section::Header, Section::Transitive, Section::Owners]
+                .into_iter()
+                .chain(opts.stats.then_some(Section::Stats))
+                .collect(),
+        ),
+        hide: Vec::new(),
+        pager: false,
+        template: None,
+    };
+
+    let nearest_manifest_path = root_manifest(None, gctx).ok();
+    let ws = nearest_manifest_path
+        .as_ref()
+        .and_then(|root| Workspace::new(root, gctx).ok());
+    let nearest_package = ws.as_ref().and_then(|ws| {
+        nearest_manifest_path
+            .as_ref()
+            .and_then(|path| ws.members().find(|p| p.manifest_path() == path))
+    });
+    let rustc_version = get_rustc_version(nearest_package, ws.as_ref(), gctx)?;
+
+    let mut crates = Vec::new();
+    for spec in specs {
+        let info = package_info(spec, gctx, reg_or_index.clone(), &compare_opts)?;
+        crates.push(compare_crate(&info, &rustc_version));
+    }
+
+    let comparison = CrateComparison {
+        crates,
+        rustc_version,
+        stats: opts.stats,
+    };
+    pager::page(gctx, opts.pager, |_| pretty_comparison(&comparison, gctx))
+}
+
+fn compare_crate(info: &PackageInfo, rustc_version: &PartialVersion) -> ComparedCrate {
+    let package = &info.package;
+    let package_id = package.package_id();
+    let features = package.summary().features();
+    let metadata = package.manifest().metadata();
+
+    let resolved_features = resolve_features(&[InternedString::new("default")], features);
+    let default_features = resolved_features
+        .iter()
+        .filter(|(name, status)| !status.is_disabled() && name.as_str() != "default")
+        .count();
+    // A dependency can be listed once per target, so count the packages.
+    let direct_dependencies = package
+        .dependencies()
+        .iter()
+        .filter(|dep| dep.kind() != DepKind::Development)
+        .filter(|dep| !dependency_status(dep, &resolved_features, features).is_disabled())
+        .map(|dep| dep.package_name())
+        .collect::<HashSet<_>>()
+        .len();
+
+    let latest = info
+        .summaries
+        .iter()
+        .filter(|s| !s.is_yanked())
+        .map(|s| s.as_summary().version())
+        .max()
+        .cloned();
+    let rust_version = package.rust_version();
+    let last_publish = info
+        .stats
+        .as_ref()
+        .and_then(|stats| stats.versions.as_ref())
+        .and_then(|versions| versions.iter().map(|v| v.created_at.as_str()).max())
+        .map(|created_at| pretty_date(created_at).to_string());
+    let transitive = info.transitive.as_ref();
+
+    ComparedCrate {
+        name: package_id.name().to_string(),
+        version: package_id.version().clone(),
+        latest,
+        rust_version: rust_version.map(|v| v.to_string()),
+        is_msrv_compatible: rust_version
+            .map(|v| v.is_compatible_with(rustc_version))
+            .unwrap_or(true),
+        license: metadata.license.clone(),
+        default_features,
+        direct_dependencies,
+        transitive_dependencies: transitive.map(|t| t.crates).unwrap_or_default(),
+        build_script: package.has_custom_build(),
+        proc_macro: package.proc_macro(),
+        transitive_build_scripts: transitive
+            .map(|t| t.build_scripts.len())
+            .unwrap_or_default(),
+        transitive_proc_macros: transitive.map(|t| t.proc_macros.len()).unwrap_or_default(),
+        transitive_not_downloaded: transitive.map(|t| t.not_downloaded).unwrap_or_default(),
+        owners: info.owners.as_ref().map(|owners| owners.len()),
+        last_publish,
+    }
+}
@@ -0,0 +1,80 @@
+use std::io::Write as _;
+
+use cargo::core::registry::PackageRegistry;
+use cargo::core::Workspace;
+use cargo::ops::RegistryOrIndex;
+use cargo::util::cache_lock::CacheLockMode;
+use cargo::util::important_paths::find_root_manifest_for_wd;
+use cargo::{ops, CargoResult, GlobalContext};
+
+use super::config::InfoConfig;
+use super::info::{get_source_id, query_summaries, RegistrySourceIds};
+use super::suggest;
+
+// Print the package specs starting with the prefix, one per line, for shell completion.
+// Crate names come from the workspace lockfile and the local index cache, and the versions
+// after `@` from the cached index entries, so that completion is fast and works offline.
+// The source replacing crates.io is followed like `cargo info` does, or the lookup fails.
+pub fn complete_spec(
+    prefix: &str,
+    gctx: &GlobalContext,
+    reg_or_index: Option<RegistryOrIndex>,
+    follow_replacement: bool,
+) -> CargoResult<()> {
+    let follow_replacement =
+        follow_replacement || InfoConfig::load(gctx)?.follow_replacement.unwrap_or(false);
+    let (_, source_ids) = get_source_id(gctx, reg_or_index, None, follow_replacement)?;
+    let candidates = match prefix.split_once('@') {
+        Some((name, _)) => version_candidates(name, gctx, &source_ids)?,
+        None => name_candidates(gctx, &source_ids),
+    };
+
+    let mut shell = gctx.shell();
+    let stdout = shell.out();
+    for candidate in candidates.iter().filter(|c| c.starts_with(prefix)) {
+        writeln!(stdout, "{candidate}")?;
+    }
+    Ok(())
+}
+
+// The names of the packages in the lockfile of the workspace, if any, and of the crates in the
+// local index cache, sorted by name.
+fn name_candidates(gctx: &GlobalContext, source_ids: &RegistrySourceIds) -> Vec<String> {
+    let mut names = suggest::cached_crate_names(gctx, source_ids.replacement);
+    // The lockfile is read as is, resolving the workspace could take a while.
+    let resolve = find_root_manifest_for_wd(gctx.cwd())
+        .and_then(|root| Workspace::new(&root, gctx))
+        .and_then(|ws| ops::load_pkg_lockfile(&ws));
+    if let Ok(Some(resolve)) = resolve {
+        names.extend(
+            resolve
+                .iter()
+                .map(|package_id| package_id.name().to_string()),
+        );
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+// The versions of the crate that are not yanked, as `name@version`, newest first.
+fn version_candidates(
+    name: &str,
+    gctx: &GlobalContext,
+    source_ids: &RegistrySourceIds,
+) -> CargoResult<Vec<String>> {
+    let mut registry = PackageRegistry::new(gctx)?;
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    registry.lock_patches();
+
+    let mut summaries = query_summaries(name, &mut registry, source_ids)?
+        .into_iter()
+        .filter(|summary| !summary.is_yanked())
+        .map(|summary| summary.into_summary())
+        .collect::<Vec<_>>();
+    summaries.sort_by(|s1, s2| s2.version().cmp(s1.version()));
+    Ok(summaries
+        .iter()
+        .map(|summary| format!("{}@{}", summary.name(), summary.version()))
+        .collect())
+}
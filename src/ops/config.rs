@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use cargo::util::context::ConfigRelativePath;
+use cargo::{CargoResult, GlobalContext};
+use serde::Deserialize;
+
+/// The `[info]` table in Cargo configuration.
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct InfoConfig {
+    /// Path to a local checkout of the RustSec advisory database.
+    pub(super) advisory_db: Option<ConfigRelativePath>,
+    /// Query through the source replacing crates.io instead of failing.
+    pub(super) follow_replacement: Option<bool>,
+    /// How long registry API responses are cached, in seconds.
+    pub(super) cache_ttl: Option<u64>,
+    /// The only sections to show by default.
+    pub(super) show: Option<Vec<String>>,
+    /// The sections to hide by default.
+    pub(super) hide: Option<Vec<String>>,
+    /// Whether to page the output when stdout is a terminal.
+    pub(super) pager: Option<bool>,
+    pub(super) colors: Option<ColorsConfig>,
+}
+
+impl InfoConfig {
+    pub(super) fn load(gctx: &GlobalContext) -> CargoResult<InfoConfig> {
+        Ok(gctx.get::<Option<InfoConfig>>("info")?.unwrap_or_default())
+    }
+}
+
+/// The `[info.colors]` table in Cargo configuration, with a style like `blue bold` for each
+/// element of the output.
+#[derive(Default, Deserialize)]
+pub(super) struct ColorsConfig {
+    /// The theme to start from: `default`, `light` or `high-contrast`.
+    pub(super) preset: Option<String>,
+    pub(super) header: Option<String>,
+    pub(super) literal: Option<String>,
+    pub(super) error: Option<String>,
+    pub(super) warn: Option<String>,
+    pub(super) note: Option<String>,
+    pub(super) disabled: Option<String>,
+    pub(super) summary: Option<String>,
+    pub(super) emphasis: Option<String>,
+}
+
+/// An entry of the `[registries]` table in Cargo configuration.
+#[derive(Deserialize)]
+struct RegistryEntry {
+    index: Option<String>,
+}
+
+// List the names of the registries with an index in the `[registries]` table, sorted by name.
+// `crates-io` is not included, it only configures the protocol there.
+pub(super) fn registry_names(gctx: &GlobalContext) -> CargoResult<Vec<String>> {
+    let registries = gctx
+        .get::<Option<BTreeMap<String, RegistryEntry>>>("registries")?
+        .unwrap_or_default();
+    Ok(registries
+        .into_iter()
+        .filter(|(name, entry)| name != "crates-io" && entry.index.is_some())
+        .map(|(name, _)| name)
+        .collect())
+}
@@ -0,0 +1,79 @@
+use cargo::core::{GitReference, SourceId};
+use cargo::util::short_hash;
+use cargo::GlobalContext;
+
+use super::date;
+
+/// Details about the git revision of a git-sourced package.
+pub(super) struct GitDetails {
+    /// The requested branch, tag or revision.
+    pub(super) reference: GitReference,
+    /// The commit the package is locked to.
+    pub(super) locked: Option<String>,
+    /// The commit from the local git database, if it has been fetched.
+    pub(super) commit: Option<CommitDetails>,
+    /// How many commits the locked commit is behind the head of the requested branch.
+    pub(super) behind: Option<usize>,
+}
+
+pub(super) struct CommitDetails {
+    /// The commit date, as `YYYY-MM-DD`.
+    pub(super) date: String,
+    pub(super) summary: String,
+}
+
+// Get the details of a git source from its precise commit and the local git database.
+// The database is only read, so missing or partial data is not an error.
+pub(super) fn details(source_id: SourceId, gctx: &GlobalContext) -> Option<GitDetails> {
+    let reference = source_id.git_reference()?.clone();
+    let locked = source_id.precise_git_fragment().map(str::to_string);
+
+    let repo = git2::Repository::open_bare(db_path(source_id, gctx)).ok();
+    let locked_oid = locked
+        .as_deref()
+        .and_then(|locked| git2::Oid::from_str(locked).ok());
+    let commit = repo.as_ref().zip(locked_oid).and_then(|(repo, oid)| {
+        let commit = repo.find_commit(oid).ok()?;
+        Some(CommitDetails {
+            date: format_date(commit.time()),
+            summary: commit.summary().unwrap_or_default().to_string(),
+        })
+    });
+    let behind = repo.as_ref().zip(locked_oid).and_then(|(repo, oid)| {
+        // Cargo fetches the branches into the remote refs of its database.
+        let head = match &reference {
+            GitReference::Branch(branch) => format!("refs/remotes/origin/{branch}"),
+            GitReference::DefaultBranch => "refs/remotes/origin/HEAD".to_string(),
+            GitReference::Tag(_) | GitReference::Rev(_) => return None,
+        };
+        let head = repo.refname_to_id(&head).ok()?;
+        let (_, behind) = repo.graph_ahead_behind(oid, head).ok()?;
+        Some(behind)
+    });
+
+    Some(GitDetails {
+        reference,
+        locked,
+        commit,
+        behind,
+    })
+}
+
+// The path of the bare repository Cargo fetches a git source into.
+// Keep in sync with the database directory name of git sources in Cargo.
+fn db_path(source_id: SourceId, gctx: &GlobalContext) -> std::path::PathBuf {
+    let canonical_url = source_id.canonical_url();
+    let name = canonical_url
+        .raw_canonicalized_url()
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("_empty");
+    let ident = format!("{name}-{}", short_hash(canonical_url));
+    gctx.git_db_path().join(ident).into_path_unlocked()
+}
+
+// Format a commit time as a `YYYY-MM-DD` date in the committer's time zone.
+fn format_date(time: git2::Time) -> String {
+    date::format_date(time.seconds() + i64::from(time.offset_minutes()) * 60)
+}
@@ -1,11 +1,17 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::task::Poll;
 
 use anyhow::{bail, Context as _};
 use cargo::core::registry::PackageRegistry;
+use cargo::core::resolver::CliFeatures;
+use cargo::core::shell::Verbosity;
 use cargo::core::PackageIdSpecQuery;
-use cargo::core::{Dependency, Package, PackageId, PackageIdSpec, Registry, SourceId, Workspace};
+use cargo::core::{
+    Dependency, Package, PackageId, PackageIdSpec, Registry, SourceId, SourceKind, Workspace,
+};
 use cargo::ops::RegistryOrIndex;
+use cargo::sources::registry::RegistryConfig;
 use cargo::sources::source::{QueryKind, Source};
 use cargo::sources::IndexSummary;
 use cargo::sources::{RegistrySource, SourceConfigMap};
@@ -13,19 +19,165 @@ use cargo::util::auth::{auth_token, AuthorizationErrorReason};
 use cargo::util::cache_lock::CacheLockMode;
 use cargo::util::command_prelude::root_manifest;
 use cargo::util::network::http::http_handle;
+use cargo::util::short_hash;
 use cargo::{ops, CargoResult, GlobalContext};
 use cargo_credential::Operation;
 use cargo_util_schemas::core::PartialVersion;
 use crates_io::Registry as CratesIoRegistry;
 use crates_io::User;
+use serde::{Deserialize, Serialize};
 
-use super::view::pretty_view;
+use super::advisories::{self, AdvisoryReport};
+use super::api::{ApiClient, CrateStats, ReverseDependencies};
+use super::cache::ApiCache;
+use super::config::InfoConfig;
+use super::git::{self, GitDetails};
+use super::pager;
+use super::provenance::{self, Provenance};
+use super::sections::{parse_sections, Section, Sections};
+use super::suggest::{self, NameConfusion, Popularity};
+use super::template::Template;
+use super::transitive::{self, TransitiveSummary};
+use super::vet::{self, VetReport};
+use super::view::{pretty_view, warn_name_confusion};
+
+pub struct InfoOptions {
+    /// Features to activate, `default` unless `--no-default-features` is passed.
+    pub cli_features: CliFeatures,
+    /// Summarize the transitive dependencies of the package.
+    pub transitive: bool,
+    /// Path to a local RustSec advisory database, overriding `info.advisory-db`.
+    pub advisory_db: Option<PathBuf>,
+    /// Show download statistics and publish history from the registry API.
+    pub stats: bool,
+    /// List the most downloaded dependents from the registry API.
+    pub reverse_deps: bool,
+    /// Query through the source replacing crates.io, overriding `info.follow-replacement`.
+    pub follow_replacement: bool,
+    /// Verify the checksum of the downloaded `.crate` file and show where it comes from.
+    pub provenance: bool,
+    /// Only show these sections, overriding `info.show` and `info.hide`.
+    pub show: Option<Vec<Section>>,
+    /// Hide these sections, in addition to `info.hide`.
+    pub hide: Vec<Section>,
+    /// Pipe the output through a pager when stdout is a terminal, unless `info.pager` is false.
+    pub pager: bool,
+    /// Render the package with this template instead of the default view.
+    pub template: Option<Template>,
+}
+
+impl InfoOptions {
+    // Whether to query through the source replacing crates.io, from the command line or the config.
+    pub(super) fn should_follow_replacement(&self, gctx: &GlobalContext) -> CargoResult<bool> {
+        Ok(self.follow_replacement || InfoConfig::load(gctx)?.follow_replacement.unwrap_or(false))
+    }
+
+    // Whether to gather a section that is only shown with its flag, which is implied when the
+    // section is selected explicitly or rendered by the template.
+    pub(super) fn wants(&self, sections: &Sections, section: Section) -> bool {
+        if let Some(template) = &self.template {
+            return template.uses_section(section);
+        }
+        let flag = match section {
+            Section::Transitive => self.transitive,
+            Section::Stats => self.stats,
+            Section::ReverseDeps => self.reverse_deps,
+            _ => true,
+        };
+        flag && sections.contains(section) || sections.is_selected(section)
+    }
+
+    // The sections to show, from the command line or the config.
+    // `--show` overrides the config entirely, while `--hide` adds to `info.hide`.
+    pub(super) fn sections(&self, gctx: &GlobalContext) -> CargoResult<Sections> {
+        if let Some(show) = &self.show {
+            return Ok(Sections::new(Some(show), &self.hide));
+        }
+        let config = InfoConfig::load(gctx)?;
+        let show = config
+            .show
+            .as_deref()
+            .map(parse_sections)
+            .transpose()
+            .context("invalid `info.show` config")?;
+        let mut hide = config
+            .hide
+            .as_deref()
+            .map(parse_sections)
+            .transpose()
+            .context("invalid `info.hide` config")?
+            .unwrap_or_default();
+        hide.extend_from_slice(&self.hide);
+        Ok(Sections::new(show.as_deref(), &hide))
+    }
+}
+
+/// Everything gathered about a package that is rendered by the view.
+pub(super) struct PackageInfo {
+    pub(super) package: Package,
+    pub(super) summaries: Vec<IndexSummary>,
+    /// The checksum of the `.crate` file, only for packages from offline sources.
+    pub(super) checksum: Option<String>,
+    pub(super) git: Option<GitDetails>,
+    pub(super) owners: Option<Vec<Owner>>,
+    pub(super) stats: Option<CrateStats>,
+    pub(super) reverse_dependencies: Option<ReverseDependencies>,
+    pub(super) name_confusion: Option<NameConfusion>,
+    pub(super) cli_features: CliFeatures,
+    pub(super) transitive: Option<TransitiveSummary>,
+    pub(super) advisories: Option<AdvisoryReport>,
+    pub(super) audits: Option<VetReport>,
+    pub(super) provenance: Option<Provenance>,
+    pub(super) suggest_cargo_tree_command: bool,
+    pub(super) sections: Sections,
+}
 
 pub fn info(
     spec: &PackageIdSpec,
     gctx: &GlobalContext,
     reg_or_index: Option<RegistryOrIndex>,
+    opts: &InfoOptions,
 ) -> CargoResult<()> {
+    let info = package_info(spec, gctx, reg_or_index, opts)?;
+    show(&info, gctx, opts)?;
+
+    // Only fail after showing the provenance, so that the mismatch can be inspected.
+    if let Some(provenance) = info.provenance.as_ref().filter(|p| p.is_mismatch()) {
+        bail!(
+            "checksum of `{}` does not match the checksum in the index",
+            provenance.crate_file.as_ref().unwrap().display()
+        );
+    }
+
+    Ok(())
+}
+
+// Render the package information with the template if any, or else with the view through
+// the pager.
+pub(super) fn show(
+    info: &PackageInfo,
+    gctx: &GlobalContext,
+    opts: &InfoOptions,
+) -> CargoResult<()> {
+    match &opts.template {
+        Some(template) => template.render_info(info, gctx),
+        None => {
+            // Warn before the pager owns the terminal, or the warning would be drawn over it.
+            if let Some(confusion) = &info.name_confusion {
+                warn_name_confusion(info.package.package_id(), confusion, &mut gctx.shell())?;
+            }
+            pager::page(gctx, opts.pager, |width| pretty_view(info, width, gctx))
+        }
+    }
+}
+
+// Gather everything about the package in the registry, to be rendered by the view.
+pub(super) fn package_info(
+    spec: &PackageIdSpec,
+    gctx: &GlobalContext,
+    reg_or_index: Option<RegistryOrIndex>,
+    opts: &InfoOptions,
+) -> CargoResult<PackageInfo> {
     let mut registry = PackageRegistry::new(gctx)?;
     // Make sure we get the lock before we download anything.
     let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
@@ -43,52 +195,166 @@ pub fn info(
             .and_then(|path| ws.members().find(|p| p.manifest_path() == path))
     });
     let (mut package_id, is_member) = find_pkgid_in_ws(nearest_package, ws.as_ref(), spec);
-    let (use_package_source_id, source_ids) = get_source_id(gctx, reg_or_index, package_id)?;
+    let (use_package_source_id, source_ids) = get_source_id(
+        gctx,
+        reg_or_index,
+        package_id,
+        opts.should_follow_replacement(gctx)?,
+    )?;
     // If we don't use the package's source, we need to query the package ID from the specified registry.
     if !use_package_source_id {
         package_id = None;
     }
 
-    let msrv_from_nearest_manifest_path_or_ws =
-        try_get_msrv_from_nearest_manifest_or_ws(nearest_package, ws.as_ref());
-    // If the workspace does not have a specific Rust version,
-    // or if the command is not called within the workspace, then fallback to the global Rust version.
-    let rustc_version = match msrv_from_nearest_manifest_path_or_ws {
-        Some(msrv) => msrv,
-        None => {
-            let current_rustc = gctx.load_global_rustc(ws.as_ref())?.version;
-            // Remove any pre-release identifiers for easier comparison.
-            // Otherwise, the MSRV check will fail if the current Rust version is a nightly or beta version.
-            semver::Version::new(
-                current_rustc.major,
-                current_rustc.minor,
-                current_rustc.patch,
-            )
-            .into()
-        }
-    };
+    let rustc_version = get_rustc_version(nearest_package, ws.as_ref(), gctx)?;
     // Only suggest cargo tree command when the package is not a workspace member.
     // For workspace members, `cargo tree --package <SPEC> --invert` is useless. It only prints itself.
     let suggest_cargo_tree_command = package_id.is_some() && !is_member;
 
-    let summaries = query_summaries(spec, &mut registry, &source_ids)?;
+    let summaries = query_summaries(spec.name(), &mut registry, &source_ids)?;
     let package_id = match package_id {
         Some(id) => id,
+        None if summaries.is_empty() => {
+            let mut msg = format!(
+                "could not find `{}` in registry `{}`",
+                spec,
+                source_ids.original.url()
+            );
+            let suggestions =
+                suggest_similar_crates(gctx, spec.name(), &mut registry, &source_ids)?;
+            if !suggestions.is_empty() {
+                msg.push_str("\n\nhelp: there are crates with a similar name:");
+                for id in suggestions {
+                    msg.push_str(&format!("\n  {}@{}", id.name(), id.version()));
+                }
+            }
+            bail!(msg)
+        }
         None => find_pkgid_in_summaries(&summaries, spec, &rustc_version, &source_ids)?,
     };
 
-    let package = registry.get(&[package_id])?;
-    let package = package.get_one(package_id)?;
-    let owners = try_list_owners(gctx, source_ids, package_id.name().as_str())?;
-    pretty_view(
-        package,
-        &summaries,
-        &owners,
+    // Don't query the registry API for hidden sections.
+    let sections = opts.sections(gctx)?;
+    // Only registry packages can be confused with another crate of the registry. The check
+    // queries the index and the registry API, so it is skipped offline and when the warning
+    // is not shown.
+    let show_name_confusion = match &opts.template {
+        Some(template) => template.uses("similar_name"),
+        None => sections.contains(Section::Header),
+    };
+    let name_confusion =
+        if show_name_confusion && !gctx.offline() && package_id.source_id().is_registry() {
+            find_name_confusion(gctx, package_id, &summaries, &mut registry, &source_ids)
+        } else {
+            None
+        };
+
+    // Resolve the dependency graph before the package is downloaded, so that the crates of
+    // the graph available locally are loaded along with it.
+    let transitive_graph = if opts.wants(&sections, Section::Transitive) {
+        Some(transitive::resolve(
+            package_id,
+            &opts.cli_features,
+            &mut registry,
+            gctx,
+        )?)
+    } else {
+        None
+    };
+    let mut package_ids = vec![package_id];
+    if let Some(graph) = &transitive_graph {
+        package_ids.extend_from_slice(graph.local_package_ids());
+    }
+    let package_set = registry.get(&package_ids)?;
+    let package = package_set.get_one(package_id)?;
+    let owners = if sections.contains(Section::Owners) {
+        try_list_owners(gctx, &source_ids, package_id.name().as_str())?
+    } else {
+        None
+    };
+    let stats = if opts.wants(&sections, Section::Stats) {
+        try_get_stats(gctx, &source_ids, package_id.name().as_str())?
+    } else {
+        None
+    };
+    let reverse_dependencies = if opts.wants(&sections, Section::ReverseDeps) {
+        try_list_reverse_dependencies(gctx, &source_ids, package_id.name().as_str())?
+    } else {
+        None
+    };
+    let transitive = transitive_graph
+        .map(|graph| graph.summarize(&package_set, gctx))
+        .transpose()?;
+    let advisory_db = match &opts.advisory_db {
+        Some(path) => Some(path.clone()),
+        None => InfoConfig::load(gctx)?
+            .advisory_db
+            .map(|path| path.resolve_path(gctx)),
+    };
+    let advisories = advisory_db
+        .map(|db| advisories::check(&db, package_id, &summaries, &rustc_version))
+        .transpose()?;
+    // Only third-party packages are audited with cargo-vet.
+    let audits = match &ws {
+        Some(ws) if package_id.source_id().is_registry() => vet::check(ws, package_id)?,
+        _ => None,
+    };
+
+    let provenance = if opts.provenance && package_id.source_id().is_registry() {
+        // Only remote registries have a download URL.
+        let download_template = if package_id.source_id().is_remote_registry() {
+            Some(registry_config(gctx, &source_ids)?.dl)
+        } else {
+            None
+        };
+        Some(provenance::verify(
+            package,
+            &summaries,
+            download_template,
+            gctx,
+        )?)
+    } else {
+        None
+    };
+    let checksum = offline_source_checksum(package_id, &summaries);
+    let git = git::details(package_id.source_id(), gctx);
+
+    Ok(PackageInfo {
+        package: package.clone(),
+        summaries,
+        checksum,
+        git,
+        owners,
+        stats,
+        reverse_dependencies,
+        name_confusion,
+        cli_features: opts.cli_features.clone(),
+        transitive,
+        advisories,
+        audits,
+        provenance,
         suggest_cargo_tree_command,
-        gctx,
-    )?;
+        sections,
+    })
+}
 
-    Ok(())
+// Get the checksum of the `.crate` file of packages from vendored directories and local
+// registries, where it can't be checked against the registry. Directory sources load it
+// from `.cargo-checksum.json`, local registries from their index.
+fn offline_source_checksum(package_id: PackageId, summaries: &[IndexSummary]) -> Option<String> {
+    let source_id = package_id.source_id();
+    if !matches!(
+        source_id.kind(),
+        SourceKind::Directory | SourceKind::LocalRegistry
+    ) {
+        return None;
+    }
+    summaries
+        .iter()
+        .map(|s| s.as_summary())
+        .find(|s| s.package_id() == package_id)
+        .and_then(|s| s.checksum())
+        .map(str::to_string)
 }
 
 fn find_pkgid_in_ws(
@@ -184,13 +450,13 @@ fn find_pkgid_in_summaries(
     }
 }
 
-fn query_summaries(
-    spec: &PackageIdSpec,
+pub(super) fn query_summaries(
+    name: &str,
     registry: &mut PackageRegistry,
     source_ids: &RegistrySourceIds,
 ) -> CargoResult<Vec<IndexSummary>> {
     // Query without version requirement to get all index summaries.
-    let dep = Dependency::parse(spec.name(), None, source_ids.original)?;
+    let dep = Dependency::parse(name, None, source_ids.original)?;
     loop {
         // Exact to avoid returning all for path/git
         match registry.query_vec(&dep, QueryKind::Exact) {
@@ -202,27 +468,202 @@ fn query_summaries(
     }
 }
 
-// Try to list the login and name of all owners of a crate.
+// Find the crates with a name similar to the requested one, in the local index cache and
+// with the registry search API, and pick their latest versions.
+fn suggest_similar_crates(
+    gctx: &GlobalContext,
+    name: &str,
+    registry: &mut PackageRegistry,
+    source_ids: &RegistrySourceIds,
+) -> CargoResult<Vec<PackageId>> {
+    const MAX_SUGGESTIONS: usize = 5;
+
+    let mut candidates = suggest::cached_crate_names(gctx, source_ids.replacement);
+    candidates.extend(try_search_crates(gctx, source_ids, name));
+    let mut suggestions = Vec::new();
+    for candidate in suggest::similar_names(name, candidates.iter().map(String::as_str)) {
+        if suggestions.len() == MAX_SUGGESTIONS {
+            break;
+        }
+        let latest = query_summaries(candidate, registry, source_ids)?
+            .into_iter()
+            .filter(|s| !s.is_yanked())
+            .map(|s| s.package_id())
+            .max_by(|id1, id2| id1.version().cmp(id2.version()));
+        suggestions.extend(latest);
+    }
+    Ok(suggestions)
+}
+
+// Look for a much more popular crate in the local index cache with a name that could be
+// confused with the package name. The download counts from the registry API are preferred,
+// with a fallback to the number of published versions.
+// This is only a guardrail, so any error is ignored. The API responses are cached, and only
+// the most similar names are looked up.
+fn find_name_confusion(
+    gctx: &GlobalContext,
+    package_id: PackageId,
+    summaries: &[IndexSummary],
+    registry: &mut PackageRegistry,
+    source_ids: &RegistrySourceIds,
+) -> Option<NameConfusion> {
+    const MAX_CANDIDATES: usize = 3;
+
+    let name = package_id.name().as_str();
+    let cached_names = suggest::cached_crate_names(gctx, source_ids.replacement);
+    let candidates = suggest::confusable_names(name, cached_names.iter().map(String::as_str));
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let mut client = if source_ids.original.is_remote_registry() {
+        api_client(gctx, source_ids).ok().flatten()
+    } else {
+        None
+    };
+    let mut downloads = |name: &str| {
+        client
+            .as_mut()
+            .and_then(|client| client.crate_stats(name).ok())
+            .map(|stats| stats.krate.downloads)
+    };
+    let own_downloads = downloads(name);
+    for candidate in candidates.into_iter().take(MAX_CANDIDATES) {
+        // Without the downloads of the package, the ones of the candidate are useless.
+        let other_downloads = own_downloads.and_then(|_| downloads(candidate));
+        let popularity = match (own_downloads, other_downloads) {
+            (Some(own), Some(other)) => Popularity::Downloads(own, other),
+            _ => Popularity::Versions(
+                summaries.len(),
+                query_summaries(candidate, registry, source_ids)
+                    .map(|summaries| summaries.len())
+                    .unwrap_or_default(),
+            ),
+        };
+        if let Some(confusion) = NameConfusion::new(candidate, popularity) {
+            return Some(confusion);
+        }
+    }
+    None
+}
+
+// Try to search the registry API for crates matching the name.
+// Suggestions are best-effort, so any error is ignored.
+fn try_search_crates(
+    gctx: &GlobalContext,
+    source_ids: &RegistrySourceIds,
+    name: &str,
+) -> Vec<String> {
+    if gctx.offline() || !source_ids.original.is_remote_registry() {
+        return Vec::new();
+    }
+    let search = || -> CargoResult<Vec<String>> {
+        let Some(mut registry) = search_registry(gctx, source_ids)? else {
+            return Ok(Vec::new());
+        };
+        let (crates, _) = registry.search(name, 20)?;
+        Ok(crates.into_iter().map(|c| c.name).collect())
+    };
+    search().unwrap_or_default()
+}
+
+// Try to list the login, name and profile URL of all owners of a crate.
 fn try_list_owners(
     gctx: &GlobalContext,
-    source_ids: RegistrySourceIds,
+    source_ids: &RegistrySourceIds,
     package_name: &str,
-) -> CargoResult<Option<Vec<String>>> {
+) -> CargoResult<Option<Vec<Owner>>> {
     // Only remote registries support listing owners.
     if !source_ids.original.is_remote_registry() {
         return Ok(None);
     }
+    let cache = ApiCache::new(gctx, source_ids.original)?;
+    let key = format!("/crates/{package_name}/owners");
+    if let Some(owners) = cache.get(&key)? {
+        return Ok(Some(owners));
+    }
+    // Without a cached list, the owners can't be listed offline.
+    if gctx.offline() {
+        return Ok(None);
+    }
     let registry = api_registry(gctx, source_ids)?;
     match registry {
         Some(mut registry) => {
-            let owners = registry.list_owners(package_name)?;
-            let names = owners.iter().map(get_username).collect();
-            Ok(Some(names))
+            let owners = registry
+                .list_owners(package_name)?
+                .iter()
+                .map(|user| Owner {
+                    name: get_username(user),
+                    url: user.url.clone(),
+                })
+                .collect::<Vec<_>>();
+            cache.put(&key, &owners);
+            Ok(Some(owners))
         }
         None => Ok(None),
     }
 }
 
+// Try to get the download statistics and publish history of a crate from the registry API.
+fn try_get_stats(
+    gctx: &GlobalContext,
+    source_ids: &RegistrySourceIds,
+    package_name: &str,
+) -> CargoResult<Option<CrateStats>> {
+    match try_api_client(gctx, source_ids, "stats")? {
+        Some(mut client) => Ok(Some(client.crate_stats(package_name)?)),
+        None => Ok(None),
+    }
+}
+
+// Try to list the most downloaded crates depending on a crate from the registry API.
+fn try_list_reverse_dependencies(
+    gctx: &GlobalContext,
+    source_ids: &RegistrySourceIds,
+    package_name: &str,
+) -> CargoResult<Option<ReverseDependencies>> {
+    // Only show the most downloaded dependents unless verbose.
+    let limit = match gctx.shell().verbosity() {
+        Verbosity::Quiet | Verbosity::Normal => 10,
+        Verbosity::Verbose => 100,
+    };
+    match try_api_client(gctx, source_ids, "reverse dependencies")? {
+        Some(mut client) => Ok(Some(client.reverse_dependencies(package_name, limit)?)),
+        None => Ok(None),
+    }
+}
+
+// Try to get a client for the registry API, warning that `what` is skipped if the registry
+// does not support the API.
+fn try_api_client<'gctx>(
+    gctx: &'gctx GlobalContext,
+    source_ids: &RegistrySourceIds,
+    what: &str,
+) -> CargoResult<Option<ApiClient<'gctx>>> {
+    // Only remote registries have an API.
+    let client = if source_ids.original.is_remote_registry() {
+        api_client(gctx, source_ids)?
+    } else {
+        None
+    };
+    if client.is_none() {
+        gctx.shell().warn(format!(
+            "registry `{}` does not support the registry API, skipping the {what}",
+            source_ids.original.display_registry_name()
+        ))?;
+    }
+    Ok(client)
+}
+
+/// An owner of a crate, as listed by the registry API.
+#[derive(Serialize, Deserialize)]
+pub(super) struct Owner {
+    /// The login, followed by the name if any.
+    pub(super) name: String,
+    /// The profile URL, e.g. on GitHub.
+    pub(super) url: Option<String>,
+}
+
 fn get_username(u: &User) -> String {
     format!(
         "{}{}",
@@ -234,9 +675,9 @@ fn get_username(u: &User) -> String {
     )
 }
 
-struct RegistrySourceIds {
+pub(super) struct RegistrySourceIds {
     /// Use when looking up the auth token, or writing out `Cargo.lock`
-    original: SourceId,
+    pub(super) original: SourceId,
     /// Use when interacting with the source (querying / publishing , etc)
     ///
     /// The source for crates.io may be replaced by a built-in source for accessing crates.io with
@@ -245,13 +686,21 @@ struct RegistrySourceIds {
     ///
     /// User-defined source replacement is not applied.
     /// Note: This will be utilized when interfacing with the registry API.
-    replacement: SourceId,
+    pub(super) replacement: SourceId,
 }
 
-fn get_source_id(
+// Get the name of the directories of a remote registry in `$CARGO_HOME/registry`, for its
+// index, `.crate` files and sources. Keep in sync with the naming in Cargo.
+pub(super) fn registry_dir_name(source_id: SourceId) -> String {
+    let ident = source_id.url().host_str().unwrap_or_default();
+    format!("{ident}-{}", short_hash(&source_id))
+}
+
+pub(super) fn get_source_id(
     gctx: &GlobalContext,
     reg_or_index: Option<RegistryOrIndex>,
     package_id: Option<PackageId>,
+    follow_replacement: bool,
 ) -> CargoResult<(bool, RegistrySourceIds)> {
     let (use_package_source_id, sid) = match (&reg_or_index, package_id) {
         (None, Some(package_id)) => (true, package_id.source_id()),
@@ -290,6 +739,27 @@ fn get_source_id(
     // Check if the user has configured source-replacement for the registry we are querying.
     if reg_or_index.is_none() && replacement_sid != builtin_replacement_sid {
         // Neither --registry nor --index was passed and the user has configured source-replacement.
+        if follow_replacement {
+            // Query the replacement source directly, so that the registry API is also the one
+            // of the replacement, if any.
+            let replacement_desc = match replacement_sid.alt_registry_key() {
+                Some(replacement_name) => format!("remote registry {replacement_name}"),
+                None => format!("non-remote-registry source {replacement_sid}"),
+            };
+            gctx.shell().note(format!(
+                "crates-io is replaced with {replacement_desc}, showing the package from it"
+            ))?;
+            let replacement_builtin_sid = SourceConfigMap::empty(gctx)?
+                .load(replacement_sid, &HashSet::new())?
+                .replaced_source_id();
+            return Ok((
+                use_package_source_id,
+                RegistrySourceIds {
+                    original: replacement_sid,
+                    replacement: replacement_builtin_sid,
+                },
+            ));
+        }
         if let Some(replacement_name) = replacement_sid.alt_registry_key() {
             bail!("crates-io is replaced with remote registry {replacement_name};\ninclude `--registry {replacement_name}` or `--registry crates-io`");
         } else {
@@ -310,27 +780,103 @@ fn get_source_id(
 // If the user is not logged in, the function will return None.
 fn api_registry(
     gctx: &GlobalContext,
-    source_ids: RegistrySourceIds,
+    source_ids: &RegistrySourceIds,
 ) -> CargoResult<Option<CratesIoRegistry>> {
-    let cfg = {
-        let mut src = RegistrySource::remote(source_ids.replacement, &HashSet::new(), gctx)?;
-        let cfg = loop {
-            match src.config()? {
-                Poll::Pending => src
-                    .block_until_ready()
-                    .with_context(|| format!("failed to update {}", source_ids.replacement))?,
-                Poll::Ready(cfg) => break cfg,
-            }
-        };
-        cfg.expect("remote registries must have config")
+    let Some((api_host, auth_required)) = api_host(gctx, source_ids)? else {
+        return Ok(None);
+    };
+    let Some(token) = api_token(gctx, source_ids)? else {
+        return Ok(None);
+    };
+
+    let handle = http_handle(gctx)?;
+    Ok(Some(CratesIoRegistry::new_handle(
+        api_host,
+        Some(token),
+        handle,
+        auth_required,
+    )))
+}
+
+// Try to get the crates.io registry which is used to search the registry API.
+// Unlike `api_registry`, a token is only needed if the registry requires authentication.
+pub(super) fn search_registry(
+    gctx: &GlobalContext,
+    source_ids: &RegistrySourceIds,
+) -> CargoResult<Option<CratesIoRegistry>> {
+    let Some((api_host, auth_required)) = api_host(gctx, source_ids)? else {
+        return Ok(None);
+    };
+    let token = api_token(gctx, source_ids)?;
+    if auth_required && token.is_none() {
+        return Ok(None);
+    }
+
+    let handle = http_handle(gctx)?;
+    Ok(Some(CratesIoRegistry::new_handle(
+        api_host,
+        token,
+        handle,
+        auth_required,
+    )))
+}
+
+// Try to get a client for the read-only endpoints of the registry API.
+// Unlike `api_registry`, a token is only needed if the registry requires authentication.
+pub(super) fn api_client<'gctx>(
+    gctx: &'gctx GlobalContext,
+    source_ids: &RegistrySourceIds,
+) -> CargoResult<Option<ApiClient<'gctx>>> {
+    let Some((api_host, auth_required)) = api_host(gctx, source_ids)? else {
+        return Ok(None);
+    };
+    let token = api_token(gctx, source_ids)?;
+    if auth_required && token.is_none() {
+        return Ok(None);
+    }
+
+    // Only cached responses can be used offline.
+    let handle = if gctx.offline() {
+        None
+    } else {
+        Some(http_handle(gctx)?)
     };
+    let cache = ApiCache::new(gctx, source_ids.original)?;
+    Ok(Some(ApiClient::new(api_host, token, handle, cache)))
+}
+
+// Get the API host of the registry and whether it requires authentication.
+fn api_host(
+    gctx: &GlobalContext,
+    source_ids: &RegistrySourceIds,
+) -> CargoResult<Option<(String, bool)>> {
+    let cfg = registry_config(gctx, source_ids)?;
     // This should only happen if the user has a custom registry configured.
     // Some registries may not have API support.
-    let api_host = match cfg.api {
-        Some(api_host) => api_host,
-        None => return Ok(None),
+    Ok(cfg.api.map(|api_host| (api_host, cfg.auth_required)))
+}
+
+// Get the `config.json` of a remote registry.
+fn registry_config(
+    gctx: &GlobalContext,
+    source_ids: &RegistrySourceIds,
+) -> CargoResult<RegistryConfig> {
+    let mut src = RegistrySource::remote(source_ids.replacement, &HashSet::new(), gctx)?;
+    let cfg = loop {
+        match src.config()? {
+            Poll::Pending => src
+                .block_until_ready()
+                .with_context(|| format!("failed to update {}", source_ids.replacement))?,
+            Poll::Ready(cfg) => break cfg,
+        }
     };
-    let token = match auth_token(
+    Ok(cfg.expect("remote registries must have config"))
+}
+
+// Get the token used to access the registry API.
+// If the user is not logged in, the function will return None.
+fn api_token(gctx: &GlobalContext, source_ids: &RegistrySourceIds) -> CargoResult<Option<String>> {
+    match auth_token(
         gctx,
         &source_ids.original,
         None,
@@ -338,7 +884,7 @@ fn api_registry(
         vec![],
         false,
     ) {
-        Ok(token) => Some(token),
+        Ok(token) => Ok(Some(token)),
         Err(err) => {
             // If the token is missing, it means the user is not logged in.
             // We don't want to show an error in this case.
@@ -349,17 +895,9 @@ fn api_registry(
             ) {
                 return Ok(None);
             }
-            return Err(err);
+            Err(err)
         }
-    };
-
-    let handle = http_handle(gctx)?;
-    Ok(Some(CratesIoRegistry::new_handle(
-        api_host,
-        token,
-        handle,
-        cfg.auth_required,
-    )))
+    }
 }
 
 fn validate_locked_and_frozen_options(
@@ -379,6 +917,33 @@ fn validate_locked_and_frozen_options(
     Ok(())
 }
 
+// Get the Rust version used to check the MSRV compatibility of packages.
+pub(super) fn get_rustc_version(
+    nearest_package: Option<&Package>,
+    ws: Option<&Workspace>,
+    gctx: &GlobalContext,
+) -> CargoResult<PartialVersion> {
+    let msrv_from_nearest_manifest_path_or_ws =
+        try_get_msrv_from_nearest_manifest_or_ws(nearest_package, ws);
+    // If the workspace does not have a specific Rust version,
+    // or if the command is not called within the workspace, then fallback to the global Rust version.
+    let rustc_version = match msrv_from_nearest_manifest_path_or_ws {
+        Some(msrv) => msrv,
+        None => {
+            let current_rustc = gctx.load_global_rustc(ws)?.version;
+            // Remove any pre-release identifiers for easier comparison.
+            // Otherwise, the MSRV check will fail if the current Rust version is a nightly or beta version.
+            semver::Version::new(
+                current_rustc.major,
+                current_rustc.minor,
+                current_rustc.patch,
+            )
+            .into()
+        }
+    };
+    Ok(rustc_version)
+}
+
 fn try_get_msrv_from_nearest_manifest_or_ws(
     nearest_package: Option<&Package>,
     ws: Option<&Workspace>,
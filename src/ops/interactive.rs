@@ -0,0 +1,626 @@
+use std::fs;
+use std::io::{self, IsTerminal as _, Stdout};
+
+use cargo::core::dependency::DepKind;
+use cargo::core::registry::PackageRegistry;
+use cargo::core::shell::Verbosity;
+use cargo::core::{Dependency, Package, PackageIdSpec};
+use cargo::ops::RegistryOrIndex;
+use cargo::util::cache_lock::CacheLockMode;
+use cargo::util::interning::InternedString;
+use cargo::{CargoResult, GlobalContext};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap};
+use ratatui::{Frame, Terminal};
+
+use semver::Version;
+
+use super::info::{get_source_id, info, package_info, query_summaries, InfoOptions, PackageInfo};
+use super::style::Theme;
+use super::view::{
+    activated_features, dependency_status, pretty_req, resolve_features, FeatureStatus,
+};
+
+const HELP: &str =
+    "←/→ tabs  ↑/↓ move  space toggle feature  enter open dependency  esc back  q quit";
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Tab {
+    Overview,
+    Features,
+    Dependencies,
+    Versions,
+    Readme,
+}
+
+impl Tab {
+    const ALL: [Tab; 5] = [
+        Tab::Overview,
+        Tab::Features,
+        Tab::Dependencies,
+        Tab::Versions,
+        Tab::Readme,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            Tab::Overview => "Overview",
+            Tab::Features => "Features",
+            Tab::Dependencies => "Dependencies",
+            Tab::Versions => "Versions",
+            Tab::Readme => "README",
+        }
+    }
+
+    fn index(self) -> usize {
+        Tab::ALL.iter().position(|&tab| tab == self).unwrap()
+    }
+}
+
+/// A visited package, with the state of its tabs.
+struct Page {
+    info: PackageInfo,
+    /// The lines of the overview tab, also bounding its scroll offset.
+    overview: Vec<Line<'static>>,
+    /// The features activated explicitly, toggled in the features tab.
+    activated: Vec<InternedString>,
+    readme: Option<String>,
+    tab: Tab,
+    /// The selected item of the list tabs, or the scroll offset of the text tabs.
+    selected: usize,
+}
+
+impl Page {
+    fn new(info: PackageInfo, theme: &Theme) -> Page {
+        let overview = overview_lines(&info, theme);
+        let features = info.package.summary().features();
+        let activated = activated_features(&info.cli_features, features);
+        let readme = read_readme(&info.package);
+        Page {
+            info,
+            overview,
+            activated,
+            readme,
+            tab: Tab::Overview,
+            selected: 0,
+        }
+    }
+
+    fn feature_names(&self) -> Vec<InternedString> {
+        self.info
+            .package
+            .summary()
+            .features()
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    fn resolved_features(&self) -> Vec<(InternedString, FeatureStatus)> {
+        resolve_features(&self.activated, self.info.package.summary().features())
+    }
+
+    // All the dependencies, normal ones first, then build and dev ones, sorted by name.
+    fn dependencies(&self) -> Vec<&Dependency> {
+        let mut dependencies = self.info.package.dependencies().iter().collect::<Vec<_>>();
+        dependencies.sort_by_key(|d| (kind_order(d.kind()), d.package_name()));
+        dependencies
+    }
+
+    // The number of items of the list tabs, or of lines of the text tabs.
+    fn len(&self) -> usize {
+        match self.tab {
+            Tab::Overview => self.overview.len(),
+            Tab::Features => self.feature_names().len(),
+            Tab::Dependencies => self.dependencies().len(),
+            Tab::Versions => self.info.summaries.len(),
+            Tab::Readme => self
+                .readme
+                .as_deref()
+                .map_or(0, |readme| readme.lines().count()),
+        }
+    }
+
+    fn switch_tab(&mut self, forward: bool) {
+        let count = Tab::ALL.len();
+        let index = if forward {
+            (self.tab.index() + 1) % count
+        } else {
+            (self.tab.index() + count - 1) % count
+        };
+        self.tab = Tab::ALL[index];
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if down {
+            self.selected = (self.selected + 1).min(self.len().saturating_sub(1));
+        } else {
+            self.selected = self.selected.saturating_sub(1);
+        }
+    }
+
+    // Activate or deactivate the selected feature, the resolved features follow.
+    fn toggle_feature(&mut self) {
+        let Some(name) = self.feature_names().get(self.selected).copied() else {
+            return;
+        };
+        match self.activated.iter().position(|&feature| feature == name) {
+            Some(index) => {
+                self.activated.remove(index);
+            }
+            None => self.activated.push(name),
+        }
+    }
+}
+
+/// The state of the browser, with the history of the visited packages.
+struct Browser<'a> {
+    gctx: &'a GlobalContext,
+    opts: &'a InfoOptions,
+    /// The visited packages, the current one last.
+    history: Vec<Page>,
+    /// A message replacing the key bindings in the status bar, e.g. a failed lookup.
+    status: Option<String>,
+    theme: Theme,
+}
+
+// Browse the package in a full-screen terminal UI.
+pub fn interactive(
+    spec: &PackageIdSpec,
+    gctx: &GlobalContext,
+    reg_or_index: Option<RegistryOrIndex>,
+    opts: &InfoOptions,
+) -> CargoResult<()> {
+    // Without a terminal, e.g. when the output is piped, show the package like `cargo info`.
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        gctx.shell()
+            .warn("`--interactive` requires a terminal, showing the package info instead")?;
+        return info(spec, gctx, reg_or_index, opts);
+    }
+    let info = package_info(spec, gctx, reg_or_index, opts)?;
+
+    // The lookups of dependencies must not print over the UI.
+    let verbosity = gctx.shell().verbosity();
+    gctx.shell().set_verbosity(Verbosity::Quiet);
+    let theme = Theme::load(gctx)?;
+    let mut browser = Browser {
+        gctx,
+        opts,
+        history: vec![Page::new(info, &theme)],
+        status: None,
+        theme,
+    };
+    let result = with_terminal(|terminal| browser.run(terminal));
+    gctx.shell().set_verbosity(verbosity);
+    result
+}
+
+/// Restores the terminal when dropped, even if the UI panics.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+// Run the UI in the alternate screen of the terminal.
+fn with_terminal(
+    run: impl FnOnce(&mut Terminal<CrosstermBackend<Stdout>>) -> CargoResult<()>,
+) -> CargoResult<()> {
+    enable_raw_mode()?;
+    let _guard = TerminalGuard;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    run(&mut terminal)
+}
+
+impl Browser<'_> {
+    fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> CargoResult<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Press && !self.handle_key(key) {
+                return Ok(());
+            }
+        }
+    }
+
+    // Update the state following the key, returns `false` to quit.
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        self.status = None;
+        let page = self.history.last_mut().unwrap();
+        match key.code {
+            KeyCode::Char('q') => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Esc | KeyCode::Backspace => {
+                if self.history.len() > 1 {
+                    self.history.pop();
+                }
+            }
+            KeyCode::Right | KeyCode::Tab => page.switch_tab(true),
+            KeyCode::Left | KeyCode::BackTab => page.switch_tab(false),
+            KeyCode::Down | KeyCode::Char('j') => page.move_selection(true),
+            KeyCode::Up | KeyCode::Char('k') => page.move_selection(false),
+            KeyCode::Char(' ') if page.tab == Tab::Features => page.toggle_feature(),
+            KeyCode::Enter if page.tab == Tab::Dependencies => self.open_dependency(),
+            _ => {}
+        }
+        true
+    }
+
+    // Look up the selected dependency the same way as the package, and show it.
+    fn open_dependency(&mut self) {
+        let page = self.history.last().unwrap();
+        let Some(dependency) = page.dependencies().get(page.selected).copied() else {
+            return;
+        };
+        let name = dependency.package_name();
+        let source_id = dependency.source_id();
+        let reg_or_index = if source_id.is_crates_io() {
+            None
+        } else if let Some(registry) = source_id.alt_registry_key() {
+            Some(RegistryOrIndex::Registry(registry.to_string()))
+        } else if source_id.is_registry() {
+            Some(RegistryOrIndex::Index(source_id.url().clone()))
+        } else {
+            self.status = Some(format!("`{name}` is not from a registry"));
+            return;
+        };
+        // Show the version the package depends on, not the latest one.
+        let version =
+            self.opts
+                .should_follow_replacement(self.gctx)
+                .and_then(|follow_replacement| {
+                    matching_version(
+                        dependency,
+                        self.gctx,
+                        reg_or_index.clone(),
+                        follow_replacement,
+                    )
+                });
+        let version = match version {
+            Ok(Some(version)) => version,
+            Ok(None) => {
+                self.status = Some(format!(
+                    "no version of `{name}` matches `{}`",
+                    dependency.version_req()
+                ));
+                return;
+            }
+            Err(err) => {
+                self.status = Some(format!("failed to look up `{name}`: {err:#}"));
+                return;
+            }
+        };
+        let spec = PackageIdSpec::new(name.to_string()).with_version(version.into());
+        match package_info(&spec, self.gctx, reg_or_index, self.opts) {
+            Ok(info) => self.history.push(Page::new(info, &self.theme)),
+            Err(err) => self.status = Some(format!("failed to look up `{name}`: {err:#}")),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame<'_>) {
+        let page = self.history.last().unwrap();
+        let theme = &self.theme;
+        let [tabs_area, content_area, status_area] = *Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(frame.size())
+        else {
+            return;
+        };
+
+        // The title is the path of the visited packages.
+        let title = self
+            .history
+            .iter()
+            .map(|page| {
+                let package_id = page.info.package.package_id();
+                format!("{}@{}", package_id.name(), package_id.version())
+            })
+            .collect::<Vec<_>>()
+            .join(" › ");
+        let tabs = Tabs::new(Tab::ALL.iter().map(|tab| tab.title()))
+            .select(page.tab.index())
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(ratatui_style(theme.header));
+        frame.render_widget(tabs, tabs_area);
+
+        match page.tab {
+            Tab::Overview => draw_text(frame, content_area, page.overview.clone(), page),
+            Tab::Features => draw_list(frame, content_area, feature_items(page, theme), page),
+            Tab::Dependencies => {
+                let items = dependency_items(page, theme);
+                draw_list(frame, content_area, items, page)
+            }
+            Tab::Versions => {
+                let items = version_items(&page.info, theme);
+                draw_list(frame, content_area, items, page)
+            }
+            Tab::Readme => {
+                let readme = page.readme.as_deref().unwrap_or("no README");
+                let lines = readme.lines().map(|line| Line::from(line.to_string()));
+                draw_text(frame, content_area, lines.collect(), page)
+            }
+        }
+
+        let status = match &self.status {
+            Some(status) => Paragraph::new(status.as_str()).style(ratatui_style(theme.error)),
+            None => Paragraph::new(HELP).style(ratatui_style(theme.disabled)),
+        };
+        frame.render_widget(status, status_area);
+    }
+}
+
+fn draw_text(frame: &mut Frame<'_>, area: Rect, lines: Vec<Line<'static>>, page: &Page) {
+    let scroll = u16::try_from(page.selected).unwrap_or(u16::MAX);
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_list(frame: &mut Frame<'_>, area: Rect, items: Vec<ListItem<'static>>, page: &Page) {
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(page.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn overview_lines(info: &PackageInfo, theme: &Theme) -> Vec<Line<'static>> {
+    let package_id = info.package.package_id();
+    let metadata = info.package.manifest().metadata();
+    let header = ratatui_style(theme.header);
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{name}: "), header),
+            Span::raw(value),
+        ])
+    };
+
+    let mut lines = vec![Line::from(Span::styled(
+        package_id.name().to_string(),
+        header,
+    ))];
+    if let Some(description) = &metadata.description {
+        lines.extend(description.lines().map(|line| Line::from(line.to_string())));
+    }
+    lines.push(field("version", package_id.version().to_string()));
+    lines.push(field(
+        "license",
+        metadata
+            .license
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+    ));
+    lines.push(field(
+        "rust-version",
+        metadata
+            .rust_version
+            .as_ref()
+            .map_or_else(|| "unknown".to_string(), |v| v.to_string()),
+    ));
+    if !metadata.keywords.is_empty() {
+        lines.push(field("keywords", metadata.keywords.join(", ")));
+    }
+    for (name, link) in [
+        ("documentation", &metadata.documentation),
+        ("homepage", &metadata.homepage),
+        ("repository", &metadata.repository),
+    ] {
+        if let Some(link) = link {
+            lines.push(field(name, link.clone()));
+        }
+    }
+    if let Some(owners) = &info.owners {
+        let owners = owners.iter().map(|owner| owner.name.as_str());
+        lines.push(field("owners", owners.collect::<Vec<_>>().join(", ")));
+    }
+    lines
+}
+
+// The features by name, with their status following the activated features.
+fn feature_items(page: &Page, theme: &Theme) -> Vec<ListItem<'static>> {
+    let features = page.info.package.summary().features();
+    let resolved = page.resolved_features();
+    page.feature_names()
+        .into_iter()
+        .map(|name| {
+            let status = resolved
+                .iter()
+                .find(|(feature, _)| *feature == name)
+                .map_or(FeatureStatus::Disabled, |(_, status)| *status);
+            let values = features[&name]
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>();
+            let marker = match status {
+                FeatureStatus::EnabledByUser => "[x]",
+                FeatureStatus::Enabled => "[+]",
+                FeatureStatus::Disabled => "[ ]",
+            };
+            let line = format!("{marker} {name} = [{}]", values.join(", "));
+            ListItem::new(line).style(status_style(status, theme))
+        })
+        .collect()
+}
+
+// The dependencies, with their status following the activated features.
+fn dependency_items(page: &Page, theme: &Theme) -> Vec<ListItem<'static>> {
+    let features = page.info.package.summary().features();
+    let resolved = page.resolved_features();
+    let dependencies = page.dependencies();
+    dependencies
+        .into_iter()
+        .map(|dependency| {
+            let status = dependency_status(dependency, &resolved, features);
+            let marker = match status {
+                FeatureStatus::EnabledByUser | FeatureStatus::Enabled => "+",
+                FeatureStatus::Disabled => " ",
+            };
+            let source_id = dependency.source_id();
+            let mut line = if source_id.is_registry() {
+                format!(
+                    "{marker} {}@{}",
+                    dependency.package_name(),
+                    pretty_req(dependency.version_req())
+                )
+            } else {
+                format!("{marker} {} ({})", dependency.package_name(), source_id)
+            };
+            match dependency.kind() {
+                DepKind::Normal => {}
+                DepKind::Build => line.push_str(" (build)"),
+                DepKind::Development => line.push_str(" (dev)"),
+            }
+            ListItem::new(line).style(status_style(status, theme))
+        })
+        .collect()
+}
+
+// The versions, newest first, with their rust-version and whether they are yanked.
+fn version_items(info: &PackageInfo, theme: &Theme) -> Vec<ListItem<'static>> {
+    let mut summaries = info.summaries.iter().collect::<Vec<_>>();
+    summaries.sort_by(|s1, s2| s2.as_summary().version().cmp(s1.as_summary().version()));
+    summaries
+        .into_iter()
+        .map(|summary| {
+            let mut line = summary.as_summary().version().to_string();
+            if let Some(rust_version) = summary.as_summary().rust_version() {
+                line.push_str(&format!("  rust-version: {rust_version}"));
+            }
+            if summary.is_yanked() {
+                line.push_str("  (yanked)");
+                return ListItem::new(line).style(ratatui_style(theme.warn));
+            }
+            ListItem::new(line)
+        })
+        .collect()
+}
+
+// Read the README of the package, from the path in the manifest or `README.md`.
+fn read_readme(package: &Package) -> Option<String> {
+    let path = package
+        .manifest()
+        .metadata()
+        .readme
+        .as_deref()
+        .unwrap_or("README.md");
+    fs::read_to_string(package.root().join(path)).ok()
+}
+
+// The highest version of the dependency matching its version requirement, preferring the
+// versions that are not yanked.
+fn matching_version(
+    dependency: &Dependency,
+    gctx: &GlobalContext,
+    reg_or_index: Option<RegistryOrIndex>,
+    follow_replacement: bool,
+) -> CargoResult<Option<Version>> {
+    let (_, source_ids) = get_source_id(gctx, reg_or_index, None, follow_replacement)?;
+    let mut registry = PackageRegistry::new(gctx)?;
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    registry.lock_patches();
+
+    let summaries = query_summaries(
+        dependency.package_name().as_str(),
+        &mut registry,
+        &source_ids,
+    )?;
+    Ok(summaries
+        .iter()
+        .filter(|s| dependency.version_req().matches(s.as_summary().version()))
+        .max_by_key(|s| (!s.is_yanked(), s.as_summary().version()))
+        .map(|s| s.as_summary().version().clone()))
+}
+
+fn kind_order(kind: DepKind) -> u8 {
+    match kind {
+        DepKind::Normal => 0,
+        DepKind::Build => 1,
+        DepKind::Development => 2,
+    }
+}
+
+fn status_style(status: FeatureStatus, theme: &Theme) -> Style {
+    match status {
+        FeatureStatus::EnabledByUser => ratatui_style(theme.header),
+        FeatureStatus::Enabled => Style::default(),
+        FeatureStatus::Disabled => ratatui_style(theme.disabled),
+    }
+}
+
+// Convert a style of the theme, so that the UI follows the `[info.colors]` config.
+fn ratatui_style(style: anstyle::Style) -> Style {
+    let mut converted = Style::default();
+    if let Some(color) = style.get_fg_color() {
+        converted = converted.fg(ratatui_color(color));
+    }
+    if let Some(color) = style.get_bg_color() {
+        converted = converted.bg(ratatui_color(color));
+    }
+    let effects = style.get_effects();
+    for (effect, modifier) in [
+        (anstyle::Effects::BOLD, Modifier::BOLD),
+        (anstyle::Effects::DIMMED, Modifier::DIM),
+        (anstyle::Effects::ITALIC, Modifier::ITALIC),
+        (anstyle::Effects::UNDERLINE, Modifier::UNDERLINED),
+        (anstyle::Effects::DOUBLE_UNDERLINE, Modifier::UNDERLINED),
+        (anstyle::Effects::CURLY_UNDERLINE, Modifier::UNDERLINED),
+        (anstyle::Effects::DOTTED_UNDERLINE, Modifier::UNDERLINED),
+        (anstyle::Effects::DASHED_UNDERLINE, Modifier::UNDERLINED),
+        (anstyle::Effects::BLINK, Modifier::SLOW_BLINK),
+        (anstyle::Effects::INVERT, Modifier::REVERSED),
+        (anstyle::Effects::HIDDEN, Modifier::HIDDEN),
+        (anstyle::Effects::STRIKETHROUGH, Modifier::CROSSED_OUT),
+    ] {
+        if effects.contains(effect) {
+            converted = converted.add_modifier(modifier);
+        }
+    }
+    converted
+}
+
+fn ratatui_color(color: anstyle::Color) -> Color {
+    match color {
+        anstyle::Color::Ansi(color) => match color {
+            anstyle::AnsiColor::Black => Color::Black,
+            anstyle::AnsiColor::Red => Color::Red,
+            anstyle::AnsiColor::Green => Color::Green,
+            anstyle::AnsiColor::Yellow => Color::Yellow,
+            anstyle::AnsiColor::Blue => Color::Blue,
+            anstyle::AnsiColor::Magenta => Color::Magenta,
+            anstyle::AnsiColor::Cyan => Color::Cyan,
+            anstyle::AnsiColor::White => Color::Gray,
+            anstyle::AnsiColor::BrightBlack => Color::DarkGray,
+            anstyle::AnsiColor::BrightRed => Color::LightRed,
+            anstyle::AnsiColor::BrightGreen => Color::LightGreen,
+            anstyle::AnsiColor::BrightYellow => Color::LightYellow,
+            anstyle::AnsiColor::BrightBlue => Color::LightBlue,
+            anstyle::AnsiColor::BrightMagenta => Color::LightMagenta,
+            anstyle::AnsiColor::BrightCyan => Color::LightCyan,
+            anstyle::AnsiColor::BrightWhite => Color::White,
+        },
+        anstyle::Color::Ansi256(color) => Color::Indexed(color.0),
+        anstyle::Color::Rgb(color) => Color::Rgb(color.0, color.1, color.2),
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::{self, IsTerminal as _};
+
+use cargo::GlobalContext;
+
+/// The narrowest space worth wrapping text into, below which a hanging indent is dropped.
+pub(super) const MIN_WRAP_WIDTH: usize = 20;
+
+// Get the width of the terminal that stdout is printed to, `None` if stdout is not a terminal.
+pub(super) fn stdout_width(gctx: &GlobalContext) -> Option<usize> {
+    // Like Cargo, let the tests force the width.
+    if let Some(width) = gctx.get_env_os("__CARGO_TEST_TTY_WIDTH_DO_NOT_USE_THIS") {
+        return width.to_str()?.parse().ok();
+    }
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+    let (columns, _) = crossterm::terminal::size().ok()?;
+    Some(usize::from(columns))
+}
+
+// Wrap the text to the width, breaking lines between words.
+// Line breaks already in the text are kept, and words longer than the width are not broken.
+pub(super) fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && text_width(&line) + 1 + text_width(word) > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+// Group the items of a comma-separated list into lines, each line taking as many items as fit.
+// The first line has `first_width` columns available and the following ones `width` columns.
+// A trailing comma or bracket is accounted for at the end of each line.
+pub(super) fn wrap_list<'a>(
+    items: &[&'a str],
+    first_width: usize,
+    width: usize,
+) -> Vec<Vec<&'a str>> {
+    let mut lines: Vec<Vec<&str>> = Vec::new();
+    let mut line = Vec::new();
+    let mut line_width = 0;
+    for &item in items {
+        let available = if lines.is_empty() { first_width } else { width };
+        let item_width = text_width(item) + 1;
+        if !line.is_empty() && line_width + 1 + item_width > available {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        if !line.is_empty() {
+            line_width += 1;
+        }
+        line_width += item_width;
+        line.push(item);
+    }
+    lines.push(line);
+    lines
+}
+
+// Lay out entries side by side in columns that fit in the width, filling each column first.
+// Returns the rows of entry indexes and the width of a column, or `None` if a single column
+// would be as wide as the width.
+pub(super) fn columns(widths: &[usize], width: usize) -> Option<(Vec<Vec<usize>>, usize)> {
+    const GAP: usize = 2;
+
+    let column_width = widths.iter().max()? + GAP;
+    let count = (width + GAP) / column_width;
+    if count < 2 {
+        return None;
+    }
+    let rows = widths.len().div_ceil(count);
+    let layout = (0..rows)
+        .map(|row| (row..widths.len()).step_by(rows).collect::<Vec<_>>())
+        .collect();
+    Some((layout, column_width))
+}
+
+// The number of columns taken by the text in a terminal.
+pub(super) fn text_width(text: &str) -> usize {
+    text.chars().count()
+}
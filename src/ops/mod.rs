@@ -1,4 +1,32 @@
-pub use info::info;
+pub use compare::compare_crates;
+pub use complete::complete_spec;
+pub use info::{info, InfoOptions};
+pub use interactive::interactive;
+pub use registries::compare_registries;
+pub use search::{search, SearchOptions};
+pub use sections::Section;
+pub use sources::{info_git, info_path, is_package_path};
+pub use template::Template;
+mod advisories;
+mod api;
+mod cache;
+mod compare;
+mod complete;
+mod config;
+mod date;
+mod git;
 pub mod info;
+mod interactive;
+mod layout;
+mod pager;
+mod provenance;
+mod registries;
+mod search;
+mod sections;
+mod sources;
 mod style;
+mod suggest;
+mod template;
+mod transitive;
+mod vet;
 mod view;
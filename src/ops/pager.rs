@@ -0,0 +1,148 @@
+use std::io::{self, IsTerminal as _};
+use std::process::{Child, Command, Stdio};
+
+use cargo::{CargoResult, GlobalContext};
+
+use super::config::InfoConfig;
+use super::layout::stdout_width;
+
+/// The pager used when neither `CARGO_PAGER` nor `PAGER` is set.
+const DEFAULT_PAGER: &str = "less -FRX";
+
+// Run the view with its output piped through the pager, if paging is enabled and stdout is a
+// terminal. Quitting the pager before the end of the output is not an error.
+//
+// The view gets the width of the terminal, measured before stdout is redirected to the pager.
+pub(super) fn page(
+    gctx: &GlobalContext,
+    enabled: bool,
+    view: impl FnOnce(Option<usize>) -> CargoResult<()>,
+) -> CargoResult<()> {
+    let width = stdout_width(gctx);
+    // The tests capture stdout, so they force paging to check which pager is started.
+    let is_terminal = io::stdout().is_terminal()
+        || gctx
+            .get_env_os("__CARGO_TEST_FORCE_PAGER_DO_NOT_USE_THIS")
+            .is_some();
+    let pager = match pager_command(gctx, enabled, is_terminal)? {
+        Some((command, is_configured)) => Pager::start(gctx, &command, is_configured)?,
+        None => None,
+    };
+    let paged = pager.is_some();
+    let result = view(width);
+    drop(pager);
+    match result {
+        Err(err) if paged && is_broken_pipe(&err) => Ok(()),
+        result => result,
+    }
+}
+
+fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<io::Error>())
+        .any(|err| err.kind() == io::ErrorKind::BrokenPipe)
+}
+
+// Get the pager from `CARGO_PAGER` or `PAGER`, or the default one, and whether it was
+// configured. Returns `None` if paging is disabled or stdout is not a terminal.
+fn pager_command(
+    gctx: &GlobalContext,
+    enabled: bool,
+    is_terminal: bool,
+) -> CargoResult<Option<(String, bool)>> {
+    if !enabled || !is_terminal || InfoConfig::load(gctx)?.pager == Some(false) {
+        return Ok(None);
+    }
+    let configured = gctx
+        .get_env_os("CARGO_PAGER")
+        .or_else(|| gctx.get_env_os("PAGER"))
+        .map(|pager| pager.to_string_lossy().into_owned());
+    let is_configured = configured.is_some();
+    let pager = configured.unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    // Like git, an empty pager or `cat` means no paging.
+    match pager.split_whitespace().next() {
+        None | Some("cat") => Ok(None),
+        Some(_) => Ok(Some((pager, is_configured))),
+    }
+}
+
+/// A running pager reading stdout, which is restored when dropped.
+struct Pager {
+    child: Child,
+    /// The original stdout, the pager writes to it.
+    #[cfg(unix)]
+    stdout: std::os::fd::OwnedFd,
+}
+
+impl Pager {
+    // Start the pager and redirect stdout to it.
+    // Returns `None` if the pager can't be started, or is not supported on this platform.
+    fn start(gctx: &GlobalContext, pager: &str, is_configured: bool) -> CargoResult<Option<Pager>> {
+        if !cfg!(unix) {
+            return Ok(None);
+        }
+        let mut words = pager.split_whitespace();
+        let Some(program) = words.next() else {
+            return Ok(None);
+        };
+
+        let mut command = Command::new(program);
+        command.args(words).stdin(Stdio::piped());
+        // Keep the colors and hyperlinks when `PAGER=less` is set without options.
+        if gctx.get_env_os("LESS").is_none() {
+            command.env("LESS", "FRX");
+        }
+        if gctx.get_env_os("LV").is_none() {
+            command.env("LV", "-c");
+        }
+        let child = match command.spawn() {
+            Ok(child) => child,
+            // A missing default pager is common enough to silently print to stdout.
+            Err(_) if !is_configured => return Ok(None),
+            Err(err) => {
+                gctx.shell()
+                    .warn(format!("failed to start the pager `{pager}`: {err}"))?;
+                return Ok(None);
+            }
+        };
+        Pager::redirect(child).map(Some)
+    }
+
+    // Point stdout at the pager. Cargo decides on colors and hyperlinks when creating its
+    // shell, so they are kept even though stdout is no longer a terminal.
+    #[cfg(unix)]
+    fn redirect(mut child: Child) -> CargoResult<Pager> {
+        use std::io::Write as _;
+        use std::os::fd::{AsFd as _, AsRawFd as _};
+
+        let stdin = child.stdin.take().unwrap();
+        io::stdout().flush()?;
+        let stdout = io::stdout().as_fd().try_clone_to_owned()?;
+        // SAFETY: both file descriptors are open, and `dup2` doesn't take ownership of them.
+        if unsafe { libc::dup2(stdin.as_raw_fd(), libc::STDOUT_FILENO) } == -1 {
+            return Err(io::Error::last_os_error().into());
+        }
+        Ok(Pager { child, stdout })
+    }
+
+    #[cfg(not(unix))]
+    fn redirect(_child: Child) -> CargoResult<Pager> {
+        unreachable!("the pager is only started on unix")
+    }
+}
+
+impl Drop for Pager {
+    fn drop(&mut self) {
+        use std::io::Write as _;
+
+        let _ = io::stdout().flush();
+        // Restoring stdout closes the pipe, so the pager sees the end of the output.
+        #[cfg(unix)]
+        {
+            use std::os::fd::AsRawFd as _;
+            // SAFETY: both file descriptors are open, and `dup2` doesn't take ownership of them.
+            unsafe { libc::dup2(self.stdout.as_raw_fd(), libc::STDOUT_FILENO) };
+        }
+        let _ = self.child.wait();
+    }
+}
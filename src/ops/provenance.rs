@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use cargo::core::Package;
+use cargo::sources::IndexSummary;
+use cargo::{CargoResult, GlobalContext};
+use cargo_util::Sha256;
+
+use super::transitive::cached_crate_path;
+
+/// Where a registry package comes from, and whether its `.crate` file matches the index.
+pub(super) struct Provenance {
+    /// The checksum of the `.crate` file recorded in the index.
+    pub(super) checksum: Option<String>,
+    /// The checksum of the cached `.crate` file.
+    pub(super) actual_checksum: Option<String>,
+    /// The download URL template from the registry `config.json`.
+    pub(super) download_template: Option<String>,
+    /// The `.crate` file in the registry cache of `CARGO_HOME`.
+    pub(super) crate_file: Option<PathBuf>,
+    /// The unpacked sources in the registry sources of `CARGO_HOME`.
+    pub(super) source_dir: PathBuf,
+}
+
+impl Provenance {
+    pub(super) fn is_mismatch(&self) -> bool {
+        matches!(
+            (&self.checksum, &self.actual_checksum),
+            (Some(expected), Some(actual)) if expected != actual
+        )
+    }
+}
+
+// Hash the cached `.crate` file of the package and compare it with the checksum of the index.
+pub(super) fn verify(
+    package: &Package,
+    summaries: &[IndexSummary],
+    download_template: Option<String>,
+    gctx: &GlobalContext,
+) -> CargoResult<Provenance> {
+    let checksum = summaries
+        .iter()
+        .map(|s| s.as_summary())
+        .find(|s| s.package_id() == package.package_id())
+        .and_then(|s| s.checksum())
+        .map(str::to_string);
+    let crate_file = cached_crate_path(package, gctx);
+    let actual_checksum = match &crate_file {
+        Some(path) => Some(Sha256::new().update_path(path)?.finish_hex()),
+        None => None,
+    };
+
+    Ok(Provenance {
+        checksum,
+        actual_checksum,
+        download_template,
+        crate_file,
+        source_dir: package.root().to_path_buf(),
+    })
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use cargo::core::registry::PackageRegistry;
+use cargo::core::PackageIdSpec;
+use cargo::ops::RegistryOrIndex;
+use cargo::util::cache_lock::CacheLockMode;
+use cargo::{CargoResult, GlobalContext};
+use semver::Version;
+
+use super::config;
+use super::info::{get_source_id, query_summaries};
+use super::view::pretty_registries;
+
+/// The crate as carried by each configured registry.
+pub(super) struct RegistryComparison {
+    pub(super) name: String,
+    pub(super) registries: Vec<RegistryListing>,
+    /// The number of versions carried by more than one registry.
+    pub(super) shared_versions: usize,
+    /// The shared versions whose checksums differ between registries.
+    pub(super) mismatches: Vec<ChecksumMismatch>,
+}
+
+pub(super) struct RegistryListing {
+    pub(super) registry: String,
+    /// The latest version that is not yanked, `None` if the registry does not carry the crate.
+    pub(super) latest: Option<Version>,
+    pub(super) versions: usize,
+    /// The error if the registry could not be queried.
+    pub(super) error: Option<anyhow::Error>,
+}
+
+pub(super) struct ChecksumMismatch {
+    pub(super) version: Version,
+    /// The checksum of the version in each registry carrying it.
+    pub(super) checksums: Vec<(String, String)>,
+}
+
+// Query the crate in crates.io and every registry in the `[registries]` table, and
+// compare what they carry.
+pub fn compare_registries(spec: &PackageIdSpec, gctx: &GlobalContext) -> CargoResult<()> {
+    let mut registry = PackageRegistry::new(gctx)?;
+    // Make sure we get the lock before we download anything.
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    registry.lock_patches();
+
+    let mut registry_names = vec!["crates-io".to_string()];
+    registry_names.extend(config::registry_names(gctx)?);
+
+    let mut registries = Vec::new();
+    // The checksums of each version, per registry.
+    let mut checksums: BTreeMap<Version, Vec<(String, String)>> = BTreeMap::new();
+    for registry_name in registry_names {
+        let summaries = get_source_id(
+            gctx,
+            Some(RegistryOrIndex::Registry(registry_name.clone())),
+            None,
+            false,
+        )
+        .and_then(|(_, source_ids)| query_summaries(spec.name(), &mut registry, &source_ids));
+        let summaries = match summaries {
+            Ok(summaries) => summaries,
+            Err(error) => {
+                registries.push(RegistryListing {
+                    registry: registry_name,
+                    latest: None,
+                    versions: 0,
+                    error: Some(error),
+                });
+                continue;
+            }
+        };
+
+        for summary in &summaries {
+            let summary = summary.as_summary();
+            if let Some(checksum) = summary.checksum() {
+                checksums
+                    .entry(summary.version().clone())
+                    .or_default()
+                    .push((registry_name.clone(), checksum.to_string()));
+            }
+        }
+        registries.push(RegistryListing {
+            registry: registry_name,
+            latest: summaries
+                .iter()
+                .filter(|s| !s.is_yanked())
+                .map(|s| s.as_summary().version())
+                .max()
+                .cloned(),
+            versions: summaries.len(),
+            error: None,
+        });
+    }
+
+    let shared = checksums
+        .into_iter()
+        .filter(|(_, checksums)| checksums.len() > 1)
+        .collect::<Vec<_>>();
+    let shared_versions = shared.len();
+    let mismatches = shared
+        .into_iter()
+        .rev()
+        .filter(|(_, checksums)| checksums.iter().any(|(_, c)| *c != checksums[0].1))
+        .map(|(version, checksums)| ChecksumMismatch { version, checksums })
+        .collect();
+
+    let comparison = RegistryComparison {
+        name: spec.name().to_string(),
+        registries,
+        shared_versions,
+        mismatches,
+    };
+    pretty_registries(&comparison, gctx)
+}
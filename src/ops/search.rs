@@ -0,0 +1,191 @@
+use std::io::{BufRead as _, IsTerminal as _};
+
+use anyhow::{bail, Context as _};
+use cargo::core::registry::PackageRegistry;
+use cargo::core::{PackageIdSpec, Workspace};
+use cargo::ops::RegistryOrIndex;
+use cargo::util::cache_lock::CacheLockMode;
+use cargo::util::command_prelude::root_manifest;
+use cargo::{CargoResult, GlobalContext};
+use cargo_util_schemas::core::PartialVersion;
+
+use super::info::{
+    api_client, get_rustc_version, get_source_id, info, query_summaries, search_registry,
+    InfoOptions,
+};
+use super::view::pretty_search;
+
+/// The number of results requested from the registry search API.
+const SEARCH_LIMIT: u32 = 10;
+
+pub struct SearchOptions {
+    /// Show the full info of the N-th search result, starting from 1.
+    pub pick: Option<usize>,
+    /// Options used to show the info of the picked package.
+    pub info: InfoOptions,
+}
+
+/// The search results, rendered as cards by the view.
+pub(super) struct SearchResults {
+    pub(super) query: String,
+    pub(super) hits: Vec<SearchHit>,
+    /// The total number of crates matching the query, including the ones not fetched.
+    pub(super) total: u32,
+    pub(super) rustc_version: PartialVersion,
+    pub(super) is_crates_io: bool,
+}
+
+pub(super) struct SearchHit {
+    pub(super) name: String,
+    pub(super) keywords: Vec<String>,
+    pub(super) description: Option<String>,
+    /// The latest version of the crate.
+    pub(super) version: String,
+    pub(super) rust_version: Option<String>,
+    /// Whether the latest version is compatible with the workspace MSRV or the current Rust version.
+    pub(super) is_msrv_compatible: bool,
+}
+
+pub fn search(
+    query: &str,
+    gctx: &GlobalContext,
+    reg_or_index: Option<RegistryOrIndex>,
+    opts: &SearchOptions,
+) -> CargoResult<()> {
+    let results = search_registry_for_hits(query, gctx, reg_or_index.clone(), &opts.info)?;
+
+    let pick = match opts.pick {
+        Some(pick) => Some(pick),
+        None => {
+            pretty_search(&results, gctx)?;
+            // Only prompt when a human is in front of the terminal.
+            if !results.hits.is_empty()
+                && std::io::stdin().is_terminal()
+                && std::io::stdout().is_terminal()
+            {
+                prompt_pick(results.hits.len(), gctx)?
+            } else {
+                None
+            }
+        }
+    };
+    let Some(pick) = pick else {
+        return Ok(());
+    };
+    let Some(hit) = pick.checked_sub(1).and_then(|i| results.hits.get(i)) else {
+        bail!(
+            "cannot pick result {pick}, the search for `{query}` returned {} results",
+            results.hits.len()
+        );
+    };
+
+    let spec = PackageIdSpec::parse(&hit.name)?;
+    info(&spec, gctx, reg_or_index, &opts.info)
+}
+
+fn search_registry_for_hits(
+    query: &str,
+    gctx: &GlobalContext,
+    reg_or_index: Option<RegistryOrIndex>,
+    opts: &InfoOptions,
+) -> CargoResult<SearchResults> {
+    let mut registry = PackageRegistry::new(gctx)?;
+    // Make sure we get the lock before we download anything.
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    registry.lock_patches();
+
+    // Check the MSRV compatibility against the workspace, like `cargo info` does.
+    let nearest_manifest_path = root_manifest(None, gctx).ok();
+    let ws = nearest_manifest_path
+        .as_ref()
+        .and_then(|root| Workspace::new(root, gctx).ok());
+    let nearest_package = ws.as_ref().and_then(|ws| {
+        nearest_manifest_path
+            .as_ref()
+            .and_then(|path| ws.members().find(|p| p.manifest_path() == path))
+    });
+    let rustc_version = get_rustc_version(nearest_package, ws.as_ref(), gctx)?;
+
+    let (_, source_ids) = get_source_id(
+        gctx,
+        reg_or_index,
+        None,
+        opts.should_follow_replacement(gctx)?,
+    )?;
+    let registry_name = source_ids.original.display_registry_name();
+    if !source_ids.original.is_remote_registry() {
+        bail!("registry `{registry_name}` does not support searching");
+    }
+    let Some(mut api) = search_registry(gctx, &source_ids)? else {
+        bail!("registry `{registry_name}` does not support the registry API");
+    };
+    let (crates, total) = api
+        .search(query, SEARCH_LIMIT)
+        .with_context(|| format!("failed to search `{query}` in registry `{registry_name}`"))?;
+
+    // The search API does not return the keywords, so get them from the crate metadata.
+    // They are only decorations, so don't fail if they are not available.
+    let mut client = api_client(gctx, &source_ids).ok().flatten();
+    let mut hits = Vec::new();
+    for krate in crates {
+        let keywords = client
+            .as_mut()
+            .and_then(|client| client.crate_stats(&krate.name).ok())
+            .map(|stats| stats.krate.keywords)
+            .unwrap_or_default();
+        let summaries = query_summaries(&krate.name, &mut registry, &source_ids)?;
+        let rust_version = summaries
+            .iter()
+            .map(|s| s.as_summary())
+            .find(|s| s.version().to_string() == krate.max_version)
+            .and_then(|s| s.rust_version().cloned());
+        hits.push(SearchHit {
+            name: krate.name,
+            keywords,
+            description: krate.description,
+            version: krate.max_version,
+            is_msrv_compatible: rust_version
+                .as_ref()
+                .map(|v| v.is_compatible_with(&rustc_version))
+                .unwrap_or(true),
+            rust_version: rust_version.map(|v| v.to_string()),
+        });
+    }
+
+    Ok(SearchResults {
+        query: query.to_string(),
+        hits,
+        total,
+        rustc_version,
+        is_crates_io: source_ids.original.is_crates_io(),
+    })
+}
+
+// Ask which result to show the full info of, `None` if the user skips it.
+fn prompt_pick(len: usize, gctx: &GlobalContext) -> CargoResult<Option<usize>> {
+    loop {
+        {
+            let mut shell = gctx.shell();
+            let stderr = shell.err();
+            write!(
+                stderr,
+                "pick a result to show its info (1-{len}), or press enter to quit: "
+            )?;
+            stderr.flush()?;
+        }
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+        match line.parse::<usize>() {
+            Ok(pick) if (1..=len).contains(&pick) => return Ok(Some(pick)),
+            _ => gctx
+                .shell()
+                .warn(format!("`{line}` is not a number between 1 and {len}"))?,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::str::FromStr;
+
+use cargo::CargoResult;
+
+/// A named section of the package info, that can be shown or hidden with `--show` and
+/// `--hide`, or the `info.show` and `info.hide` config.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Section {
+    /// The name, description, version, license and rust-version.
+    Header,
+    /// The documentation, homepage, repository and crates.io links.
+    Links,
+    Features,
+    Dependencies,
+    Transitive,
+    Advisories,
+    Audits,
+    Provenance,
+    Owners,
+    Stats,
+    ReverseDeps,
+    /// Hints about related commands.
+    Notes,
+}
+
+impl Section {
+    const ALL: &'static [Section] = &[
+        Section::Header,
+        Section::Links,
+        Section::Features,
+        Section::Dependencies,
+        Section::Transitive,
+        Section::Advisories,
+        Section::Audits,
+        Section::Provenance,
+        Section::Owners,
+        Section::Stats,
+        Section::ReverseDeps,
+        Section::Notes,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Section::Header => "header",
+            Section::Links => "links",
+            Section::Features => "features",
+            Section::Dependencies => "dependencies",
+            Section::Transitive => "transitive",
+            Section::Advisories => "advisories",
+            Section::Audits => "audits",
+            Section::Provenance => "provenance",
+            Section::Owners => "owners",
+            Section::Stats => "stats",
+            Section::ReverseDeps => "reverse-deps",
+            Section::Notes => "notes",
+        }
+    }
+}
+
+impl FromStr for Section {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `deps` is accepted as a shorthand, like in `cargo tree`.
+        if s == "deps" {
+            return Ok(Section::Dependencies);
+        }
+        Section::ALL
+            .iter()
+            .copied()
+            .find(|section| section.name() == s)
+            .ok_or_else(|| {
+                let names = Section::ALL
+                    .iter()
+                    .map(|section| section.name())
+                    .collect::<Vec<_>>();
+                anyhow::format_err!(
+                    "unknown section `{s}`, expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// The sections of the package info to print.
+pub(super) struct Sections {
+    shown: Vec<Section>,
+    /// Whether the sections were selected explicitly rather than all shown by default.
+    selected: bool,
+}
+
+impl Sections {
+    // Select the sections to show, starting from the given ones or all of them.
+    pub(super) fn new(show: Option<&[Section]>, hide: &[Section]) -> Sections {
+        let shown = show
+            .unwrap_or(Section::ALL)
+            .iter()
+            .copied()
+            .filter(|section| !hide.contains(section))
+            .collect();
+        Sections {
+            shown,
+            selected: show.is_some(),
+        }
+    }
+
+    pub(super) fn contains(&self, section: Section) -> bool {
+        self.shown.contains(&section)
+    }
+
+    // Whether the section was explicitly asked for, e.g. to show it even when it is only
+    // shown in verbose mode by default.
+    pub(super) fn is_selected(&self, section: Section) -> bool {
+        self.selected && self.contains(section)
+    }
+
+    // Keep the content of the section only if it is shown.
+    pub(super) fn filter<'a, T>(&self, section: Section, content: &'a Option<T>) -> Option<&'a T> {
+        content.as_ref().filter(|_| self.contains(section))
+    }
+}
+
+// Parse the section names of the `info.show` or `info.hide` config.
+pub(super) fn parse_sections(names: &[String]) -> CargoResult<Vec<Section>> {
+    names.iter().map(|name| name.parse()).collect()
+}
@@ -0,0 +1,151 @@
+use std::path::Path;
+
+use anyhow::Context as _;
+use cargo::core::registry::PackageRegistry;
+use cargo::core::PackageIdSpecQuery;
+use cargo::core::{Dependency, GitReference, Package, PackageIdSpec, Registry, SourceId};
+use cargo::sources::source::QueryKind;
+use cargo::sources::{IndexSummary, PathSource};
+use cargo::util::cache_lock::CacheLockMode;
+use cargo::util::IntoUrl;
+use cargo::{CargoResult, GlobalContext};
+
+use super::git;
+use super::info::{show, InfoOptions, PackageInfo};
+use super::sections::Section;
+use super::transitive::{self, TransitiveSummary};
+
+// Whether the package argument is a path to a local package rather than a package ID spec.
+pub fn is_package_path(package: &str) -> bool {
+    !package.contains("://")
+        && (package.starts_with('.') || package.contains('/') || package.contains('\\'))
+}
+
+// Inspect the package at a local path, which doesn't need to be published anywhere.
+pub fn info_path(path: &Path, gctx: &GlobalContext, opts: &InfoOptions) -> CargoResult<()> {
+    let path = cargo_util::paths::normalize_path(&gctx.cwd().join(path));
+    // Accept both the package directory and its manifest.
+    let root = if path.ends_with("Cargo.toml") {
+        path.parent().unwrap_or(&path).to_path_buf()
+    } else {
+        path
+    };
+    if !root.join("Cargo.toml").is_file() {
+        anyhow::bail!("could not find `Cargo.toml` in `{}`", root.display());
+    }
+
+    let source_id = SourceId::for_path(&root)?;
+    let mut source = PathSource::new(&root, source_id, gctx);
+    let package = source
+        .root_package()
+        .with_context(|| format!("failed to load the package at `{}`", root.display()))?;
+
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    let transitive = if wants_transitive(gctx, opts)? {
+        let mut registry = PackageRegistry::new(gctx)?;
+        registry.lock_patches();
+        let graph = transitive::resolve(
+            package.package_id(),
+            &opts.cli_features,
+            &mut registry,
+            gctx,
+        )?;
+        let package_set = registry.get(graph.local_package_ids())?;
+        Some(graph.summarize(&package_set, gctx)?)
+    } else {
+        None
+    };
+    show_package(package, Vec::new(), transitive, gctx, opts)
+}
+
+// Inspect a package from a git repository, at the given branch, tag or revision.
+pub fn info_git(
+    spec: &PackageIdSpec,
+    url: &str,
+    reference: GitReference,
+    gctx: &GlobalContext,
+    opts: &InfoOptions,
+) -> CargoResult<()> {
+    let url = url.into_url()?;
+    let source_id = SourceId::for_git(&url, reference)?;
+
+    let mut registry = PackageRegistry::new(gctx)?;
+    // Make sure we get the lock before we download anything.
+    let _lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+    registry.lock_patches();
+
+    let dep = Dependency::parse(spec.name(), None, source_id)?;
+    let summaries = loop {
+        match registry.query_vec(&dep, QueryKind::Exact) {
+            std::task::Poll::Ready(res) => break res?,
+            std::task::Poll::Pending => registry.block_until_ready()?,
+        }
+    };
+    let Some(package_id) = summaries
+        .iter()
+        .map(|s| s.package_id())
+        .filter(|&id| spec.matches(id))
+        .max_by(|id1, id2| id1.version().cmp(id2.version()))
+    else {
+        anyhow::bail!("could not find `{spec}` in git repository `{url}`");
+    };
+
+    // Resolve the dependency graph before the package is downloaded, so that the crates of
+    // the graph available locally are loaded along with it.
+    let transitive_graph = if wants_transitive(gctx, opts)? {
+        Some(transitive::resolve(
+            package_id,
+            &opts.cli_features,
+            &mut registry,
+            gctx,
+        )?)
+    } else {
+        None
+    };
+    let mut package_ids = vec![package_id];
+    if let Some(graph) = &transitive_graph {
+        package_ids.extend_from_slice(graph.local_package_ids());
+    }
+    let package_set = registry.get(&package_ids)?;
+    let package = package_set.get_one(package_id)?.clone();
+    let transitive = transitive_graph
+        .map(|graph| graph.summarize(&package_set, gctx))
+        .transpose()?;
+    show_package(package, summaries, transitive, gctx, opts)
+}
+
+// Whether the transitive dependencies are summarized and shown.
+fn wants_transitive(gctx: &GlobalContext, opts: &InfoOptions) -> CargoResult<bool> {
+    Ok(opts.wants(&opts.sections(gctx)?, Section::Transitive))
+}
+
+// Render a package that doesn't come from a registry, where only the information from
+// its manifest and dependency graph applies.
+fn show_package(
+    package: Package,
+    summaries: Vec<IndexSummary>,
+    transitive: Option<TransitiveSummary>,
+    gctx: &GlobalContext,
+    opts: &InfoOptions,
+) -> CargoResult<()> {
+    let sections = opts.sections(gctx)?;
+    let git = git::details(package.package_id().source_id(), gctx);
+    let info = PackageInfo {
+        package,
+        summaries,
+        checksum: None,
+        git,
+        owners: None,
+        stats: None,
+        reverse_dependencies: None,
+        name_confusion: None,
+        cli_features: opts.cli_features.clone(),
+        transitive,
+        advisories: None,
+        audits: None,
+        provenance: None,
+        suggest_cargo_tree_command: false,
+        sections,
+    };
+    show(&info, gctx, opts)
+}
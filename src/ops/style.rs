@@ -1,8 +1,197 @@
-use anstyle::{AnsiColor, Effects, Style};
-
-pub const NOP: Style = Style::new();
-pub const HEADER: Style = AnsiColor::Green.on_default().effects(Effects::BOLD);
-pub const LITERAL: Style = AnsiColor::Cyan.on_default().effects(Effects::BOLD);
-pub const ERROR: Style = AnsiColor::Red.on_default().effects(Effects::BOLD);
-pub const WARN: Style = AnsiColor::Yellow.on_default().effects(Effects::BOLD);
-pub const NOTE: Style = AnsiColor::Cyan.on_default().effects(Effects::BOLD);
+use std::fmt;
+
+use anstyle::{AnsiColor, Color, Effects, Style};
+use anyhow::Context as _;
+use cargo::{CargoResult, GlobalContext};
+
+use super::config::{ColorsConfig, InfoConfig};
+
+/// The styles of the info output, customizable with the `[info.colors]` config.
+#[derive(Copy, Clone, Debug)]
+pub(super) struct Theme {
+    /// Field names and section titles, also used for features enabled by the user.
+    pub(super) header: Style,
+    /// Commands and values to copy as is.
+    pub(super) literal: Style,
+    pub(super) error: Style,
+    pub(super) warn: Style,
+    pub(super) note: Style,
+    /// Disabled features and optional dependencies.
+    pub(super) disabled: Style,
+    /// Summaries of the items not shown, e.g. `3 deactivated features`.
+    pub(super) summary: Style,
+    /// Punctuation following a label, e.g. the colon of `note:`.
+    pub(super) emphasis: Style,
+    /// Whether stdout supports OSC 8 hyperlinks.
+    hyperlinks: bool,
+}
+
+impl Theme {
+    // The default theme, matching the colors of Cargo.
+    const DEFAULT: Theme = Theme {
+        header: AnsiColor::Green.on_default().effects(Effects::BOLD),
+        literal: AnsiColor::Cyan.on_default().effects(Effects::BOLD),
+        error: AnsiColor::Red.on_default().effects(Effects::BOLD),
+        warn: AnsiColor::Yellow.on_default().effects(Effects::BOLD),
+        note: AnsiColor::Cyan.on_default().effects(Effects::BOLD),
+        disabled: Style::new().effects(Effects::DIMMED),
+        summary: Style::new().effects(Effects::ITALIC),
+        emphasis: Style::new().effects(Effects::BOLD),
+        hyperlinks: false,
+    };
+
+    // A theme for terminals with a light background, avoiding yellow and cyan text.
+    const LIGHT: Theme = Theme {
+        header: AnsiColor::Blue.on_default().effects(Effects::BOLD),
+        literal: AnsiColor::Magenta.on_default().effects(Effects::BOLD),
+        error: AnsiColor::Red.on_default().effects(Effects::BOLD),
+        warn: AnsiColor::Magenta.on_default().effects(Effects::BOLD),
+        note: AnsiColor::Blue.on_default().effects(Effects::BOLD),
+        disabled: AnsiColor::BrightBlack.on_default(),
+        summary: Style::new().effects(Effects::ITALIC),
+        emphasis: Style::new().effects(Effects::BOLD),
+        hyperlinks: false,
+    };
+
+    // A high-contrast theme that doesn't rely on telling red from green, nor on dimmed text.
+    const HIGH_CONTRAST: Theme = Theme {
+        header: AnsiColor::BrightBlue.on_default().effects(Effects::BOLD),
+        literal: AnsiColor::BrightCyan.on_default().effects(Effects::BOLD),
+        error: AnsiColor::BrightMagenta
+            .on_default()
+            .effects(Effects::BOLD.insert(Effects::UNDERLINE)),
+        warn: AnsiColor::BrightYellow.on_default().effects(Effects::BOLD),
+        note: AnsiColor::BrightBlue.on_default().effects(Effects::BOLD),
+        disabled: Style::new().effects(Effects::ITALIC),
+        summary: Style::new().effects(Effects::UNDERLINE),
+        emphasis: Style::new().effects(Effects::BOLD),
+        hyperlinks: false,
+    };
+
+    // Load the theme from the `[info.colors]` config, starting from the preset if any.
+    pub(super) fn load(gctx: &GlobalContext) -> CargoResult<Theme> {
+        let mut theme = match InfoConfig::load(gctx)?.colors {
+            Some(colors) => Theme::from_config(&colors)?,
+            None => Theme::DEFAULT,
+        };
+        // Cargo only tells whether hyperlinks are supported by rendering one, which is
+        // empty if they are not, following `term.hyperlinks` and the terminal support.
+        theme.hyperlinks = !gctx.shell().out_hyperlink("").to_string().is_empty();
+        Ok(theme)
+    }
+
+    // Link the text printed between `{link}` and `{link:#}` to the URL, if hyperlinks are
+    // supported.
+    pub(super) fn link(&self, url: impl Into<String>) -> Link {
+        Link {
+            url: self.hyperlinks.then(|| url.into()),
+        }
+    }
+
+    fn from_config(colors: &ColorsConfig) -> CargoResult<Theme> {
+        let mut theme = match colors.preset.as_deref() {
+            None | Some("default") => Theme::DEFAULT,
+            Some("light") => Theme::LIGHT,
+            Some("high-contrast") => Theme::HIGH_CONTRAST,
+            Some(preset) => anyhow::bail!(
+                "invalid `info.colors.preset` config: unknown preset `{preset}`, \
+                 expected one of: default, light, high-contrast"
+            ),
+        };
+        for (key, style, spec) in [
+            ("header", &mut theme.header, &colors.header),
+            ("literal", &mut theme.literal, &colors.literal),
+            ("error", &mut theme.error, &colors.error),
+            ("warn", &mut theme.warn, &colors.warn),
+            ("note", &mut theme.note, &colors.note),
+            ("disabled", &mut theme.disabled, &colors.disabled),
+            ("summary", &mut theme.summary, &colors.summary),
+            ("emphasis", &mut theme.emphasis, &colors.emphasis),
+        ] {
+            if let Some(spec) = spec {
+                *style = parse_style(spec)
+                    .with_context(|| format!("invalid `info.colors.{key}` config"))?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::DEFAULT
+    }
+}
+
+// Parse a style like `blue bold` or `black on yellow`, made of a foreground color, effects and
+// a background color after `on`. An empty style is the default style of the terminal.
+fn parse_style(spec: &str) -> CargoResult<Style> {
+    let mut style = Style::new();
+    let mut words = spec.split_whitespace();
+    while let Some(word) = words.next() {
+        if word == "on" {
+            let Some(color) = words.next() else {
+                anyhow::bail!("expected a background color after `on` in `{spec}`");
+            };
+            style = style.bg_color(Some(parse_color(color)?));
+            continue;
+        }
+        let effect = match word {
+            "bold" => Effects::BOLD,
+            "dimmed" => Effects::DIMMED,
+            "italic" => Effects::ITALIC,
+            "underline" => Effects::UNDERLINE,
+            "strikethrough" => Effects::STRIKETHROUGH,
+            "plain" => Effects::new(),
+            _ => {
+                style = style.fg_color(Some(parse_color(word)?));
+                continue;
+            }
+        };
+        style = style.effects(style.get_effects().insert(effect));
+    }
+    Ok(style)
+}
+
+fn parse_color(name: &str) -> CargoResult<Color> {
+    let color = match name {
+        "black" => AnsiColor::Black,
+        "red" => AnsiColor::Red,
+        "green" => AnsiColor::Green,
+        "yellow" => AnsiColor::Yellow,
+        "blue" => AnsiColor::Blue,
+        "magenta" => AnsiColor::Magenta,
+        "cyan" => AnsiColor::Cyan,
+        "white" => AnsiColor::White,
+        "bright-black" => AnsiColor::BrightBlack,
+        "bright-red" => AnsiColor::BrightRed,
+        "bright-green" => AnsiColor::BrightGreen,
+        "bright-yellow" => AnsiColor::BrightYellow,
+        "bright-blue" => AnsiColor::BrightBlue,
+        "bright-magenta" => AnsiColor::BrightMagenta,
+        "bright-cyan" => AnsiColor::BrightCyan,
+        "bright-white" => AnsiColor::BrightWhite,
+        _ => anyhow::bail!("unknown color or effect `{name}`"),
+    };
+    Ok(color.into())
+}
+
+/// An OSC 8 hyperlink, rendered like a style: `{link}` starts it and `{link:#}` ends it.
+#[derive(Default)]
+pub(super) struct Link {
+    /// `None` if hyperlinks are not supported, then nothing is rendered.
+    url: Option<String>,
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(url) = &self.url else {
+            return Ok(());
+        };
+        if f.alternate() {
+            write!(f, "\x1B]8;;\x1B\\")
+        } else {
+            write!(f, "\x1B]8;;{url}\x1B\\")
+        }
+    }
+}
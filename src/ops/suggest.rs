@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::Path;
+
+use cargo::core::SourceId;
+use cargo::util::edit_distance::edit_distance;
+use cargo::GlobalContext;
+
+use super::info::registry_dir_name;
+
+/// Prefixes and suffixes commonly added to or dropped from crate names.
+/// `-sys` is not one of them, `foo-sys` is the conventional name of the bindings used by `foo`.
+const PREFIXES: &[&str] = &["rust-", "lib-"];
+const SUFFIXES: &[&str] = &["-rs", "-rust", "-lib"];
+
+/// How much more popular a crate with a confusable name is.
+pub(super) enum Popularity {
+    /// Downloads of the package and of the other crate, from the registry API.
+    Downloads(u64, u64),
+    /// Published versions of the package and of the other crate, from the index.
+    Versions(usize, usize),
+}
+
+impl Popularity {
+    fn is_much_more_popular(&self) -> bool {
+        match *self {
+            Popularity::Downloads(own, other) => other >= 10 * own.max(1),
+            Popularity::Versions(own, other) => other >= 10 && other >= 5 * own,
+        }
+    }
+}
+
+/// A much more popular crate whose name could be confused with the package name.
+pub(super) struct NameConfusion {
+    pub(super) name: String,
+    pub(super) popularity: Popularity,
+}
+
+impl NameConfusion {
+    // Returns the confusion only if the other crate is much more popular.
+    pub(super) fn new(name: &str, popularity: Popularity) -> Option<NameConfusion> {
+        popularity.is_much_more_popular().then(|| NameConfusion {
+            name: name.to_string(),
+            popularity,
+        })
+    }
+}
+
+// List the names of all crates cached in the local index of the registry.
+// Only the crates that have been queried before are cached, so this is a subset of the registry.
+pub(super) fn cached_crate_names(gctx: &GlobalContext, source_id: SourceId) -> Vec<String> {
+    let index_dir = registry_dir_name(source_id);
+    let index_path = gctx.registry_index_path().into_path_unlocked();
+
+    let mut names = Vec::new();
+    for dir in [index_dir.clone(), format!("{index_dir}-shallow")] {
+        collect_cached_crate_names(&index_path.join(dir).join(".cache"), &mut names);
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn collect_cached_crate_names(dir: &Path, names: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_cached_crate_names(&path, names);
+        } else if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+            // Skip files that are not index entries, crate names never contain a dot.
+            if !name.contains('.') {
+                names.push(name.to_string());
+            }
+        }
+    }
+}
+
+// Rank the candidates by similarity to the name, most similar first.
+// Candidates that are not similar enough, or identical to the name, are dropped.
+pub(super) fn similar_names<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let mut similar = candidates
+        .into_iter()
+        .filter(|&candidate| candidate != name)
+        .filter_map(|candidate| distance(name, candidate).map(|d| (d, candidate)))
+        .collect::<Vec<_>>();
+    similar.sort();
+    similar.dedup();
+    similar
+        .into_iter()
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+// Keep the candidates whose name is easily confused with the name, e.g. `serde-json` and
+// `serde_json`, or `reqwest` and `request`, most similar first.
+pub(super) fn confusable_names<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    // Short names are too close to each other to tell a typo from a different crate.
+    let max_distance = if normalize(name).len() < 5 { 1 } else { 2 };
+    similar_names(name, candidates)
+        .into_iter()
+        .filter(|candidate| distance(name, candidate).is_some_and(|d| d <= max_distance))
+        .collect()
+}
+
+// The distance between two crate names. Names that only differ by `-` and `_`, or by a
+// common prefix or suffix, are considered closer than any typo.
+fn distance(name: &str, candidate: &str) -> Option<usize> {
+    let name = normalize(name);
+    let candidate = normalize(candidate);
+    if name == candidate {
+        return Some(0);
+    }
+    if strip_affixes(&name) == strip_affixes(&candidate) {
+        return Some(1);
+    }
+    let limit = std::cmp::max(name.len(), 3) / 3;
+    edit_distance(&name, &candidate, limit).map(|d| d + 1)
+}
+
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace('_', "-")
+}
+
+fn strip_affixes(name: &str) -> &str {
+    let name = PREFIXES
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name);
+    SUFFIXES
+        .iter()
+        .find_map(|suffix| name.strip_suffix(suffix))
+        .unwrap_or(name)
+}
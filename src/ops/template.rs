@@ -0,0 +1,496 @@
+use std::collections::BTreeMap;
+use std::io::Write as _;
+use std::str::FromStr;
+
+use anyhow::bail;
+use cargo::core::dependency::DepKind;
+use cargo::core::{GitReference, PackageId};
+use cargo::util::human_readable_bytes;
+use cargo::{CargoResult, GlobalContext};
+
+use super::info::PackageInfo;
+use super::sections::Section;
+use super::vet::VetStatus;
+use super::view::{
+    activated_features, dependency_status, pretty_date, pretty_package_source, pretty_req,
+    pretty_source, resolve_features, FeatureStatus,
+};
+
+/// A custom output template, like `{name}@{version} {license}`.
+///
+/// `{field}` is replaced with a field of the package, and `{#field}...{/field}` repeats its body
+/// for each item of a list, or renders it once if the field is set and not `false`. Inside the
+/// body, the fields of the item come first. `{{`, `}}`, `\n` and `\t` are escapes.
+#[derive(Clone, Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Text(String),
+    Field(String),
+    Section(String, Vec<Node>),
+}
+
+impl FromStr for Template {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> CargoResult<Template> {
+        // The open sections, with the nodes parsed so far, the whole template first.
+        let mut stack: Vec<(String, Vec<Node>)> = vec![(String::new(), Vec::new())];
+        let mut text = String::new();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                    text.push(c);
+                }
+                ('\\', Some('n')) => {
+                    chars.next();
+                    text.push('\n');
+                }
+                ('\\', Some('t')) => {
+                    chars.next();
+                    text.push('\t');
+                }
+                ('\\', Some('\\')) => {
+                    chars.next();
+                    text.push('\\');
+                }
+                ('{', _) => {
+                    let mut tag = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => tag.push(c),
+                            None => bail!("unclosed `{{` in template"),
+                        }
+                    }
+                    let nodes = &mut stack.last_mut().unwrap().1;
+                    if !text.is_empty() {
+                        nodes.push(Node::Text(std::mem::take(&mut text)));
+                    }
+                    if let Some(name) = tag.strip_prefix('#') {
+                        stack.push((parse_name(name, &tag)?, Vec::new()));
+                    } else if let Some(name) = tag.strip_prefix('/') {
+                        if stack.len() == 1 || stack.last().unwrap().0 != name {
+                            bail!("unexpected `{{{tag}}}` in template");
+                        }
+                        let (name, body) = stack.pop().unwrap();
+                        stack.last_mut().unwrap().1.push(Node::Section(name, body));
+                    } else {
+                        nodes.push(Node::Field(parse_name(&tag, &tag)?));
+                    }
+                }
+                ('}', _) => bail!("unmatched `}}` in template, use `}}}}` for a literal `}}`"),
+                _ => text.push(c),
+            }
+        }
+
+        if stack.len() > 1 {
+            bail!("unclosed `{{#{}}}` in template", stack.last().unwrap().0);
+        }
+        let (_, mut nodes) = stack.pop().unwrap();
+        if !text.is_empty() {
+            nodes.push(Node::Text(text));
+        }
+        Ok(Template { nodes })
+    }
+}
+
+fn parse_name(name: &str, tag: &str) -> CargoResult<String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+        bail!("invalid placeholder `{{{tag}}}` in template");
+    }
+    Ok(name.to_string())
+}
+
+/// The value of a field: text, or a list of items with their own fields.
+enum Value {
+    Text(String),
+    List(Vec<Fields>),
+}
+
+type Fields = BTreeMap<&'static str, Value>;
+
+impl Template {
+    // Render the package information with the template, followed by a newline if the
+    // template doesn't end with one.
+    pub(super) fn render_info(&self, info: &PackageInfo, gctx: &GlobalContext) -> CargoResult<()> {
+        let fields = package_fields(info, gctx);
+        let mut output = String::new();
+        render_nodes(&self.nodes, &mut vec![&fields], &mut output)?;
+        if !output.ends_with('\n') {
+            output.push('\n');
+        }
+        gctx.shell().out().write_all(output.as_bytes())?;
+        Ok(())
+    }
+
+    // Whether the template renders the field, to skip gathering what is not shown.
+    pub(super) fn uses(&self, field: &str) -> bool {
+        fn uses(nodes: &[Node], field: &str) -> bool {
+            nodes.iter().any(|node| match node {
+                Node::Text(_) => false,
+                Node::Field(name) => name == field,
+                Node::Section(name, body) => name == field || uses(body, field),
+            })
+        }
+        uses(&self.nodes, field)
+    }
+
+    // Whether the template renders a field of the section, to gather it without its flag.
+    pub(super) fn uses_section(&self, section: Section) -> bool {
+        let fields: &[&str] = match section {
+            Section::Transitive => &[
+                "transitive_crates",
+                "transitive_not_downloaded",
+                "transitive_download_size",
+                "transitive_licenses",
+                "transitive_rust_version",
+                "build_scripts",
+                "proc_macros",
+            ],
+            Section::Stats => &["downloads", "recent_downloads", "created", "updated"],
+            Section::ReverseDeps => &["dependents_total", "dependents"],
+            _ => return true,
+        };
+        fields.iter().any(|field| self.uses(field))
+    }
+}
+
+fn render_nodes<'a>(
+    nodes: &[Node],
+    scopes: &mut Vec<&'a Fields>,
+    output: &mut String,
+) -> CargoResult<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Field(name) => match lookup(scopes, name)? {
+                Value::Text(text) => output.push_str(text),
+                // A list on its own is rendered as the names of its items.
+                Value::List(items) => {
+                    let names = items.iter().filter_map(|item| match item.get("name") {
+                        Some(Value::Text(name)) => Some(name.as_str()),
+                        _ => None,
+                    });
+                    output.push_str(&names.collect::<Vec<_>>().join(", "));
+                }
+            },
+            Node::Section(name, body) => match lookup(scopes, name)? {
+                Value::Text(text) => {
+                    if !text.is_empty() && text != "false" {
+                        render_nodes(body, scopes, output)?;
+                    }
+                }
+                Value::List(items) => {
+                    for item in items {
+                        scopes.push(item);
+                        render_nodes(body, scopes, output)?;
+                        scopes.pop();
+                    }
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+// Look up the field in the innermost item first, then in the enclosing ones.
+fn lookup<'a>(scopes: &[&'a Fields], name: &str) -> CargoResult<&'a Value> {
+    if let Some(value) = scopes.iter().rev().find_map(|&fields| fields.get(name)) {
+        return Ok(value);
+    }
+    let expected = scopes.last().unwrap().keys().copied();
+    bail!(
+        "unknown placeholder `{{{name}}}` in template, expected one of: {}",
+        expected.collect::<Vec<_>>().join(", ")
+    )
+}
+
+// Gather every field of the package information. Missing values are empty.
+fn package_fields(info: &PackageInfo, gctx: &GlobalContext) -> Fields {
+    let package = &info.package;
+    let summary = package.manifest().summary();
+    let package_id = summary.package_id();
+    let metadata = package.manifest().metadata();
+    let feature_map = summary.features();
+    let activated = activated_features(&info.cli_features, feature_map);
+    let resolved = resolve_features(&activated, feature_map);
+
+    let mut fields = Fields::new();
+    fields.insert("name", text(package_id.name()));
+    fields.insert("version", text(package_id.version()));
+    fields.insert("description", optional(metadata.description.as_ref()));
+    fields.insert("license", optional(metadata.license.as_ref()));
+    fields.insert("license_file", optional(metadata.license_file.as_ref()));
+    fields.insert("rust_version", optional(metadata.rust_version.as_ref()));
+    fields.insert("edition", text(package.manifest().edition()));
+    fields.insert("documentation", optional(metadata.documentation.as_ref()));
+    fields.insert("homepage", optional(metadata.homepage.as_ref()));
+    fields.insert("repository", optional(metadata.repository.as_ref()));
+    fields.insert("authors", names(&metadata.authors));
+    fields.insert("keywords", names(&metadata.keywords));
+    fields.insert("categories", names(&metadata.categories));
+    fields.insert(
+        "source",
+        text(pretty_package_source(summary.source_id(), gctx)),
+    );
+    fields.insert("checksum", optional(info.checksum.as_ref()));
+    let latest = info
+        .summaries
+        .iter()
+        .filter(|s| !s.is_yanked())
+        .map(|s| s.as_summary().version())
+        .max();
+    fields.insert("latest", optional(latest));
+
+    // The publish dates are only known with `--stats`.
+    let published = info
+        .stats
+        .iter()
+        .flat_map(|stats| stats.versions.iter().flatten());
+    let mut versions = info.summaries.iter().collect::<Vec<_>>();
+    versions.sort_by(|s1, s2| s2.as_summary().version().cmp(s1.as_summary().version()));
+    let versions = versions.into_iter().map(|s| {
+        let version = s.as_summary().version().to_string();
+        let data = published.clone().find(|data| data.num == version);
+        Fields::from([
+            ("name", text(&version)),
+            ("yanked", text(s.is_yanked())),
+            ("rust_version", optional(s.as_summary().rust_version())),
+            (
+                "published",
+                optional(data.map(|data| pretty_date(&data.created_at))),
+            ),
+            (
+                "published_by",
+                optional(
+                    data.and_then(|data| data.published_by.as_ref())
+                        .map(|p| &p.login),
+                ),
+            ),
+        ])
+    });
+    fields.insert("versions", Value::List(versions.collect()));
+
+    let features = resolved.iter().map(|(name, status)| {
+        let values = feature_map[name].iter().map(|value| value.to_string());
+        Fields::from([
+            ("name", text(name)),
+            ("values", names(values)),
+            ("enabled", text(!status.is_disabled())),
+            ("activated", text(*status == FeatureStatus::EnabledByUser)),
+        ])
+    });
+    let mut features = features.collect::<Vec<_>>();
+    features.sort_by(|f1, f2| name_of(f1).cmp(name_of(f2)));
+    fields.insert("features", Value::List(features));
+
+    let dependencies = package.dependencies().iter().map(|dependency| {
+        let kind = match dependency.kind() {
+            DepKind::Normal => "normal",
+            DepKind::Build => "build",
+            DepKind::Development => "dev",
+        };
+        let status = dependency_status(dependency, &resolved, feature_map);
+        Fields::from([
+            ("name", text(dependency.package_name())),
+            ("req", text(pretty_req(dependency.version_req()))),
+            ("kind", text(kind)),
+            ("optional", text(dependency.is_optional())),
+            ("enabled", text(!status.is_disabled())),
+            ("target", optional(dependency.platform())),
+            ("source", text(pretty_source(dependency.source_id(), gctx))),
+        ])
+    });
+    fields.insert("dependencies", Value::List(dependencies.collect()));
+
+    let owners = info.owners.iter().flatten().map(|owner| {
+        Fields::from([
+            ("name", text(&owner.name)),
+            ("url", optional(owner.url.as_ref())),
+        ])
+    });
+    fields.insert("owners", Value::List(owners.collect()));
+
+    let git = info.git.as_ref();
+    let commit = git.and_then(|git| git.commit.as_ref());
+    let (branch, tag, rev) = match git.map(|git| &git.reference) {
+        Some(GitReference::Branch(branch)) => (Some(branch), None, None),
+        Some(GitReference::Tag(tag)) => (None, Some(tag), None),
+        Some(GitReference::Rev(rev)) => (None, None, Some(rev)),
+        Some(GitReference::DefaultBranch) | None => (None, None, None),
+    };
+    fields.insert("git_branch", optional(branch));
+    fields.insert("git_tag", optional(tag));
+    fields.insert("git_rev", optional(rev));
+    fields.insert(
+        "git_commit",
+        optional(git.and_then(|git| git.locked.as_ref())),
+    );
+    fields.insert("git_date", optional(commit.map(|commit| &commit.date)));
+    fields.insert(
+        "git_summary",
+        optional(commit.map(|commit| &commit.summary)),
+    );
+    fields.insert("git_behind", optional(git.and_then(|git| git.behind)));
+
+    let krate = info.stats.as_ref().map(|stats| &stats.krate);
+    fields.insert("downloads", optional(krate.map(|krate| krate.downloads)));
+    fields.insert(
+        "recent_downloads",
+        optional(krate.and_then(|krate| krate.recent_downloads)),
+    );
+    fields.insert(
+        "created",
+        optional(krate.map(|krate| pretty_date(&krate.created_at))),
+    );
+    fields.insert(
+        "updated",
+        optional(krate.map(|krate| pretty_date(&krate.updated_at))),
+    );
+
+    let reverse_dependencies = info.reverse_dependencies.as_ref();
+    fields.insert(
+        "dependents_total",
+        optional(reverse_dependencies.map(|r| r.total)),
+    );
+    let dependents = reverse_dependencies.iter().flat_map(|r| &r.dependents);
+    let dependents = dependents.map(|dependent| {
+        Fields::from([
+            ("name", text(&dependent.name)),
+            ("version", text(&dependent.version)),
+            ("req", text(&dependent.req)),
+            ("optional", text(dependent.optional)),
+            ("kind", optional(dependent.kind.as_ref())),
+            ("downloads", text(dependent.downloads)),
+        ])
+    });
+    fields.insert("dependents", Value::List(dependents.collect()));
+
+    let transitive = info.transitive.as_ref();
+    fields.insert(
+        "transitive_crates",
+        optional(transitive.map(|transitive| transitive.crates)),
+    );
+    fields.insert(
+        "transitive_not_downloaded",
+        optional(transitive.map(|transitive| transitive.not_downloaded)),
+    );
+    fields.insert(
+        "transitive_download_size",
+        optional(transitive.map(|transitive| {
+            let (size, unit) = human_readable_bytes(transitive.download_size);
+            format!("{size:.1}{unit}")
+        })),
+    );
+    let licenses = transitive
+        .iter()
+        .flat_map(|transitive| &transitive.licenses);
+    let licenses = licenses
+        .map(|(license, count)| Fields::from([("name", text(license)), ("count", text(count))]));
+    fields.insert("transitive_licenses", Value::List(licenses.collect()));
+    fields.insert(
+        "transitive_rust_version",
+        optional(
+            transitive
+                .and_then(|transitive| transitive.max_rust_version.as_ref())
+                .map(|(rust_version, _)| rust_version),
+        ),
+    );
+    let build_scripts = transitive.iter().flat_map(|t| &t.build_scripts);
+    fields.insert("build_scripts", names(build_scripts.map(pretty_id)));
+    let proc_macros = transitive.iter().flat_map(|t| &t.proc_macros);
+    fields.insert("proc_macros", names(proc_macros.map(pretty_id)));
+
+    let advisories = info.advisories.iter().flat_map(|report| &report.advisories);
+    let advisories = advisories.map(|advisory| {
+        Fields::from([
+            ("name", text(&advisory.id)),
+            ("title", text(&advisory.title)),
+            ("severity", optional(advisory.severity)),
+            ("informational", optional(advisory.informational.as_ref())),
+            ("patched", names(&advisory.patched)),
+        ])
+    });
+    fields.insert("advisories", Value::List(advisories.collect()));
+    fields.insert(
+        "recommended_version",
+        optional(
+            info.advisories
+                .as_ref()
+                .and_then(|report| report.recommended)
+                .map(|package_id| package_id.version().clone()),
+        ),
+    );
+
+    let audits = info.audits.iter().flat_map(|report| &report.criteria);
+    let audits = audits.map(|criteria| {
+        let status = match criteria.status {
+            VetStatus::Audited => "audited",
+            VetStatus::Exempted => "exempted",
+            VetStatus::Unaudited => "unaudited",
+        };
+        Fields::from([("name", text(&criteria.name)), ("status", text(status))])
+    });
+    fields.insert("audits", Value::List(audits.collect()));
+
+    let provenance = info.provenance.as_ref();
+    fields.insert(
+        "crate_file",
+        optional(
+            provenance
+                .and_then(|p| p.crate_file.as_ref())
+                .map(|path| path.display()),
+        ),
+    );
+    fields.insert(
+        "crate_checksum",
+        optional(provenance.and_then(|p| p.actual_checksum.as_ref())),
+    );
+    fields.insert(
+        "checksum_mismatch",
+        optional(provenance.map(|p| p.is_mismatch())),
+    );
+    fields.insert(
+        "download_template",
+        optional(provenance.and_then(|p| p.download_template.as_ref())),
+    );
+
+    fields.insert(
+        "similar_name",
+        optional(info.name_confusion.as_ref().map(|c| &c.name)),
+    );
+    fields
+}
+
+fn text(value: impl ToString) -> Value {
+    Value::Text(value.to_string())
+}
+
+fn optional(value: Option<impl ToString>) -> Value {
+    Value::Text(value.map(|value| value.to_string()).unwrap_or_default())
+}
+
+// A list of items that only have a name.
+fn names(values: impl IntoIterator<Item = impl ToString>) -> Value {
+    let items = values
+        .into_iter()
+        .map(|value| Fields::from([("name", text(value))]));
+    Value::List(items.collect())
+}
+
+fn pretty_id(package_id: &PackageId) -> String {
+    format!("{}@{}", package_id.name(), package_id.version())
+}
+
+fn name_of(fields: &Fields) -> &str {
+    match fields.get("name") {
+        Some(Value::Text(name)) => name,
+        _ => "",
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::task::Poll;
+
+use anyhow::Context as _;
+use cargo::core::registry::PackageRegistry;
+use cargo::core::resolver::features::RequestedFeatures;
+use cargo::core::resolver::{self, CliFeatures, ResolveOpts, ResolveVersion, VersionPreferences};
+use cargo::core::{Dependency, Package, PackageId, PackageSet, Registry as _, Summary};
+use cargo::sources::source::QueryKind;
+use cargo::sources::SourceConfigMap;
+use cargo::{CargoResult, GlobalContext};
+use cargo_util_schemas::core::PartialVersion;
+
+use super::info::registry_dir_name;
+
+/// A summary of the transitive dependency graph of a package.
+///
+/// Nothing is downloaded for the summary: the `rust-version` comes from the index, and what
+/// needs a manifest from the crates that are available locally.
+pub(super) struct TransitiveSummary {
+    /// Number of unique crates in the graph, excluding the package itself.
+    pub(super) crates: usize,
+    /// Number of crates that are not downloaded yet, so their manifest is not inspected.
+    pub(super) not_downloaded: usize,
+    /// Total size of the `.crate` files of the downloaded registry dependencies.
+    pub(super) download_size: u64,
+    /// Licenses and the number of downloaded crates using them, most used first.
+    pub(super) licenses: Vec<(String, usize)>,
+    /// The highest `rust-version` in the graph and the package that requires it.
+    pub(super) max_rust_version: Option<(PartialVersion, PackageId)>,
+    /// Downloaded packages with a build script.
+    pub(super) build_scripts: Vec<PackageId>,
+    /// Downloaded packages that are procedural macros.
+    pub(super) proc_macros: Vec<PackageId>,
+}
+
+/// The dependency graph of a package, resolved from the index before anything is downloaded.
+pub(super) struct TransitiveGraph {
+    /// The crates of the graph, excluding the package itself.
+    package_ids: Vec<PackageId>,
+    /// The crates that can be loaded without downloading them: path dependencies, crates of
+    /// local registries and registry crates whose `.crate` file is cached.
+    local_package_ids: Vec<PackageId>,
+    max_rust_version: Option<(PartialVersion, PackageId)>,
+}
+
+// Resolve the full dependency graph of the package in isolation, as if it were the root
+// of its own workspace.
+pub(super) fn resolve(
+    package_id: PackageId,
+    cli_features: &CliFeatures,
+    registry: &mut PackageRegistry<'_>,
+    gctx: &GlobalContext,
+) -> CargoResult<TransitiveGraph> {
+    let summary = query_summary(package_id, registry)?;
+    let opts = ResolveOpts::new(false, RequestedFeatures::CliFeatures(cli_features.clone()));
+    let resolve = resolver::resolve(
+        &[(summary, opts)],
+        &[],
+        registry,
+        &VersionPreferences::default(),
+        ResolveVersion::default(),
+        Some(gctx),
+    )?;
+
+    let package_ids = resolve
+        .iter()
+        .filter(|&id| id != package_id)
+        .collect::<Vec<_>>();
+
+    let mut max_rust_version: Option<(PartialVersion, PackageId)> = None;
+    for &id in &package_ids {
+        let Some(rust_version) = resolve.summary(id).rust_version() else {
+            continue;
+        };
+        let rust_version = rust_version.as_partial();
+        let is_newer = max_rust_version
+            .as_ref()
+            .map(|(max, _)| version_key(rust_version) > version_key(max))
+            .unwrap_or(true);
+        if is_newer {
+            max_rust_version = Some((rust_version.clone(), id));
+        }
+    }
+
+    let source_config = SourceConfigMap::new(gctx)?;
+    let mut local_package_ids = Vec::new();
+    for &id in &package_ids {
+        if is_available_locally(id, &source_config, gctx)? {
+            local_package_ids.push(id);
+        }
+    }
+
+    Ok(TransitiveGraph {
+        package_ids,
+        local_package_ids,
+        max_rust_version,
+    })
+}
+
+impl TransitiveGraph {
+    // The crates to load along with the package, see `summarize`.
+    pub(super) fn local_package_ids(&self) -> &[PackageId] {
+        &self.local_package_ids
+    }
+
+    // Summarize the graph from the manifests of the crates available locally, which must
+    // be in the package set.
+    pub(super) fn summarize(
+        self,
+        package_set: &PackageSet<'_>,
+        gctx: &GlobalContext,
+    ) -> CargoResult<TransitiveSummary> {
+        let packages = package_set.get_many(self.local_package_ids.iter().copied())?;
+
+        let mut download_size = 0;
+        let mut licenses = HashMap::new();
+        let mut build_scripts = Vec::new();
+        let mut proc_macros = Vec::new();
+        for package in &packages {
+            if let Some(size) = cached_crate_path(package, gctx)
+                .and_then(|path| path.metadata().ok())
+                .map(|metadata| metadata.len())
+            {
+                download_size += size;
+            }
+
+            let license = package
+                .manifest()
+                .metadata()
+                .license
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string());
+            *licenses.entry(license).or_insert(0) += 1;
+
+            if package.has_custom_build() {
+                build_scripts.push(package.package_id());
+            }
+            if package.proc_macro() {
+                proc_macros.push(package.package_id());
+            }
+        }
+
+        let mut licenses = licenses.into_iter().collect::<Vec<_>>();
+        licenses.sort_by(|(l1, c1), (l2, c2)| c2.cmp(c1).then_with(|| l1.cmp(l2)));
+        build_scripts.sort();
+        proc_macros.sort();
+
+        Ok(TransitiveSummary {
+            crates: self.package_ids.len(),
+            not_downloaded: self.package_ids.len() - packages.len(),
+            download_size,
+            licenses,
+            max_rust_version: self.max_rust_version,
+            build_scripts,
+            proc_macros,
+        })
+    }
+}
+
+// Query the summary of the package, so that its dependencies are resolved before it is
+// downloaded.
+fn query_summary(
+    package_id: PackageId,
+    registry: &mut PackageRegistry<'_>,
+) -> CargoResult<Summary> {
+    // A yanked version is still shown when it is locked.
+    registry.add_to_yanked_whitelist(std::iter::once(package_id));
+    let dep = Dependency::parse(package_id.name(), None, package_id.source_id())?;
+    let summaries = loop {
+        match registry.query_vec(&dep, QueryKind::Exact) {
+            Poll::Ready(res) => break res?,
+            Poll::Pending => registry.block_until_ready()?,
+        }
+    };
+    summaries
+        .into_iter()
+        .map(|summary| summary.into_summary())
+        .find(|summary| summary.package_id() == package_id)
+        .with_context(|| format!("could not find `{package_id}` to resolve its dependencies"))
+}
+
+// Whether the package can be loaded without downloading it. Git dependencies would need
+// a checkout, so they are never available.
+fn is_available_locally(
+    package_id: PackageId,
+    source_config: &SourceConfigMap<'_>,
+    gctx: &GlobalContext,
+) -> CargoResult<bool> {
+    let source_id = package_id.source_id();
+    if source_id.is_path() {
+        return Ok(true);
+    }
+    if !source_id.is_registry() {
+        return Ok(false);
+    }
+    let source_id = source_config
+        .load(source_id, &HashSet::new())?
+        .replaced_source_id();
+    if !source_id.is_remote_registry() {
+        return Ok(true);
+    }
+    let path = gctx
+        .registry_cache_path()
+        .join(registry_dir_name(source_id))
+        .into_path_unlocked()
+        .join(format!(
+            "{}-{}.crate",
+            package_id.name(),
+            package_id.version()
+        ));
+    Ok(path.exists())
+}
+
+// Get the path of the downloaded `.crate` file of a registry package.
+//
+// Registry packages are extracted to `$CARGO_HOME/registry/src/<registry>/<name>-<version>`,
+// and the `.crate` file is kept in `$CARGO_HOME/registry/cache/<registry>/<name>-<version>.crate`.
+pub(super) fn cached_crate_path(package: &Package, gctx: &GlobalContext) -> Option<PathBuf> {
+    if !package.package_id().source_id().is_registry() {
+        return None;
+    }
+    let registry_dir = package.root().parent()?.file_name()?;
+    let path = gctx
+        .registry_cache_path()
+        .join(registry_dir)
+        .into_path_unlocked()
+        .join(format!("{}-{}.crate", package.name(), package.version()));
+    path.exists().then_some(path)
+}
+
+fn version_key(version: &PartialVersion) -> (u64, u64, u64) {
+    (
+        version.major,
+        version.minor.unwrap_or_default(),
+        version.patch.unwrap_or_default(),
+    )
+}
@@ -0,0 +1,396 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use cargo::core::{PackageId, Workspace};
+use cargo::CargoResult;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+const SAFE_TO_DEPLOY: &str = "safe-to-deploy";
+const SAFE_TO_RUN: &str = "safe-to-run";
+
+/// The cargo-vet audit status of a package version, per criteria.
+pub(super) struct VetReport {
+    pub(super) criteria: Vec<CriteriaStatus>,
+    /// Whether wildcard audits or trusted publishers of the package could not be checked,
+    /// because the publisher of the version is not recorded in `imports.lock`.
+    pub(super) unknown_publisher: bool,
+}
+
+pub(super) struct CriteriaStatus {
+    pub(super) name: String,
+    pub(super) status: VetStatus,
+    /// The audits and exemptions that certify the version, starting from a full audit or
+    /// an exemption, followed by the delta audits leading to the version.
+    pub(super) chain: Vec<VetStep>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum VetStatus {
+    Audited,
+    Exempted,
+    Unaudited,
+}
+
+#[derive(Clone)]
+pub(super) struct VetStep {
+    pub(super) kind: VetStepKind,
+    /// The name of the peer the audit is imported from, `None` for local audits.
+    pub(super) import: Option<String>,
+}
+
+#[derive(Clone)]
+pub(super) enum VetStepKind {
+    Full(String),
+    Delta(String, String),
+    Exemption(String),
+    /// A wildcard audit of the versions published by a user, with the version and its publisher.
+    Wildcard(String, String),
+    /// A publisher trusted for the criteria, with the version and its publisher.
+    Trusted(String, String),
+}
+
+#[derive(Default, Deserialize)]
+struct AuditsFile {
+    #[serde(default)]
+    criteria: BTreeMap<String, CriteriaEntry>,
+    #[serde(default)]
+    audits: BTreeMap<String, Vec<AuditEntry>>,
+    #[serde(default, rename = "wildcard-audits")]
+    wildcard_audits: BTreeMap<String, Vec<PublisherAuditEntry>>,
+    #[serde(default)]
+    trusted: BTreeMap<String, Vec<PublisherAuditEntry>>,
+}
+
+#[derive(Deserialize)]
+struct CriteriaEntry {
+    implies: Option<CriteriaNames>,
+}
+
+#[derive(Deserialize)]
+struct AuditEntry {
+    criteria: CriteriaNames,
+    version: Option<String>,
+    delta: Option<String>,
+    violation: Option<String>,
+}
+
+/// A wildcard audit or a trusted entry, certifying the versions published by the user
+/// between the start and end dates.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PublisherAuditEntry {
+    criteria: CriteriaNames,
+    user_id: u64,
+    start: String,
+    end: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    exemptions: BTreeMap<String, Vec<ExemptionEntry>>,
+}
+
+#[derive(Deserialize)]
+struct ExemptionEntry {
+    version: String,
+    criteria: CriteriaNames,
+}
+
+#[derive(Default, Deserialize)]
+struct ImportsLock {
+    #[serde(default)]
+    audits: BTreeMap<String, AuditsFile>,
+    /// The publishers of the versions, recorded by cargo-vet for the wildcard audits.
+    #[serde(default)]
+    publisher: BTreeMap<String, Vec<PublisherEntry>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PublisherEntry {
+    version: String,
+    when: String,
+    user_id: u64,
+    user_login: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CriteriaNames {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl CriteriaNames {
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        let names = match self {
+            CriteriaNames::One(name) => std::slice::from_ref(name),
+            CriteriaNames::Many(names) => names.as_slice(),
+        };
+        names.iter().map(String::as_str)
+    }
+}
+
+// An audit or exemption of the package, with the criteria it certifies.
+struct Certification {
+    step: VetStep,
+    criteria: HashSet<String>,
+}
+
+// Read the cargo-vet store of the workspace and check the audit status of the package.
+// Returns `None` if the workspace does not use cargo-vet.
+pub(super) fn check(ws: &Workspace<'_>, package_id: PackageId) -> CargoResult<Option<VetReport>> {
+    let store = store_path(ws);
+    if !store.join("audits.toml").is_file() {
+        return Ok(None);
+    }
+
+    let audits: AuditsFile = read_toml(&store.join("audits.toml"))?;
+    let config: ConfigFile = read_toml(&store.join("config.toml"))?;
+    let imports: ImportsLock = read_toml(&store.join("imports.lock"))?;
+
+    let package_name = package_id.name().as_str();
+    let mut certifications = certifications_from_audits(&audits, package_name, None);
+    for (peer, audits) in &imports.audits {
+        certifications.extend(certifications_from_audits(
+            audits,
+            package_name,
+            Some(peer.as_str()),
+        ));
+    }
+    let version = normalize_version(&package_id.version().to_string());
+    let publisher = imports
+        .publisher
+        .get(package_name)
+        .into_iter()
+        .flatten()
+        .find(|publisher| normalize_version(&publisher.version) == version);
+    // Wildcard audits and trusted publishers certify the versions published by a user, the
+    // publisher of the version is known from the `imports.lock` of cargo-vet.
+    let mut publisher_audits = vec![
+        (&audits.wildcard_audits, &audits.criteria, None, false),
+        (&audits.trusted, &audits.criteria, None, true),
+    ];
+    for (peer, audits) in &imports.audits {
+        publisher_audits.push((
+            &audits.wildcard_audits,
+            &audits.criteria,
+            Some(peer.as_str()),
+            false,
+        ));
+    }
+    let has_publisher_audits = publisher_audits
+        .iter()
+        .any(|(entries, ..)| entries.contains_key(package_name));
+    let unknown_publisher = has_publisher_audits && publisher.is_none();
+    if let Some(publisher) = publisher {
+        for (entries, definitions, import, is_trusted) in publisher_audits {
+            let entries = entries.get(package_name).into_iter().flatten();
+            certifications.extend(entries.filter_map(|entry| {
+                certify_publisher(entry, &version, publisher, definitions, import, is_trusted)
+            }));
+        }
+    }
+    for exemption in config.exemptions.get(package_name).into_iter().flatten() {
+        certifications.push(Certification {
+            step: VetStep {
+                kind: VetStepKind::Exemption(normalize_version(&exemption.version)),
+                import: None,
+            },
+            criteria: implied_criteria(exemption.criteria.iter(), &audits.criteria),
+        });
+    }
+
+    let mut criteria_names = vec![SAFE_TO_DEPLOY.to_string(), SAFE_TO_RUN.to_string()];
+    criteria_names.extend(audits.criteria.keys().cloned());
+    let criteria = criteria_names
+        .into_iter()
+        .map(|name| {
+            let (status, chain) = match find_chain(&certifications, &name, &version, false) {
+                Some(chain) => (VetStatus::Audited, chain),
+                None => match find_chain(&certifications, &name, &version, true) {
+                    Some(chain) => (VetStatus::Exempted, chain),
+                    None => (VetStatus::Unaudited, Vec::new()),
+                },
+            };
+            CriteriaStatus {
+                name,
+                status,
+                chain,
+            }
+        })
+        .collect();
+
+    Ok(Some(VetReport {
+        criteria,
+        unknown_publisher,
+    }))
+}
+
+// Get the directory of the cargo-vet store, `supply-chain` unless overridden with `store.path`
+// in `[workspace.metadata.vet]` or in `[package.metadata.vet]` of the root package.
+fn store_path(ws: &Workspace<'_>) -> PathBuf {
+    let root_package = ws.members().find(|package| package.root() == ws.root());
+    let path = ws
+        .custom_metadata()
+        .or_else(|| root_package?.manifest().custom_metadata())
+        .and_then(|metadata| metadata.get("vet"))
+        .and_then(|vet| vet.get("store"))
+        .and_then(|store| store.get("path"))
+        .and_then(|path| path.as_str());
+    ws.root().join(path.unwrap_or("supply-chain"))
+}
+
+fn read_toml<T: DeserializeOwned + Default>(path: &Path) -> CargoResult<T> {
+    if !path.is_file() {
+        return Ok(T::default());
+    }
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content).with_context(|| format!("failed to parse `{}`", path.display()))
+}
+
+fn certifications_from_audits(
+    audits: &AuditsFile,
+    name: &str,
+    import: Option<&str>,
+) -> Vec<Certification> {
+    audits
+        .audits
+        .get(name)
+        .into_iter()
+        .flatten()
+        .filter(|audit| audit.violation.is_none())
+        .filter_map(|audit| {
+            let kind = match (&audit.version, &audit.delta) {
+                (Some(version), None) => VetStepKind::Full(normalize_version(version)),
+                (None, Some(delta)) => {
+                    let (from, to) = delta.split_once("->")?;
+                    VetStepKind::Delta(normalize_version(from), normalize_version(to))
+                }
+                _ => return None,
+            };
+            Some(Certification {
+                step: VetStep {
+                    kind,
+                    import: import.map(str::to_string),
+                },
+                criteria: implied_criteria(audit.criteria.iter(), &audits.criteria),
+            })
+        })
+        .collect()
+}
+
+// Certify the version with a wildcard audit or a trusted entry, if it was published by its
+// user between its start and end dates.
+fn certify_publisher(
+    entry: &PublisherAuditEntry,
+    version: &str,
+    publisher: &PublisherEntry,
+    definitions: &BTreeMap<String, CriteriaEntry>,
+    import: Option<&str>,
+    is_trusted: bool,
+) -> Option<Certification> {
+    let when = publisher.when.as_str();
+    let is_in_range =
+        entry.start.as_str() <= when && entry.end.as_deref().map_or(true, |end| when < end);
+    if entry.user_id != publisher.user_id || !is_in_range {
+        return None;
+    }
+    let login = match &publisher.user_login {
+        Some(login) => login.clone(),
+        None => format!("user {}", publisher.user_id),
+    };
+    let kind = if is_trusted {
+        VetStepKind::Trusted(version.to_string(), login)
+    } else {
+        VetStepKind::Wildcard(version.to_string(), login)
+    };
+    Some(Certification {
+        step: VetStep {
+            kind,
+            import: import.map(str::to_string),
+        },
+        criteria: implied_criteria(entry.criteria.iter(), definitions),
+    })
+}
+
+// Expand the criteria to all the criteria they imply, `safe-to-deploy` implies `safe-to-run`.
+fn implied_criteria<'a>(
+    criteria: impl Iterator<Item = &'a str>,
+    definitions: &BTreeMap<String, CriteriaEntry>,
+) -> HashSet<String> {
+    let mut implied = HashSet::new();
+    let mut queue = criteria.map(str::to_string).collect::<Vec<_>>();
+    while let Some(name) = queue.pop() {
+        if !implied.insert(name.clone()) {
+            continue;
+        }
+        if name == SAFE_TO_DEPLOY {
+            queue.push(SAFE_TO_RUN.to_string());
+        }
+        if let Some(implies) = definitions.get(&name).and_then(|c| c.implies.as_ref()) {
+            queue.extend(implies.iter().map(str::to_string));
+        }
+    }
+    implied
+}
+
+// Find the shortest chain of a full audit (or an exemption) followed by delta audits
+// that certifies the version for the criteria.
+fn find_chain(
+    certifications: &[Certification],
+    criteria: &str,
+    version: &str,
+    with_exemptions: bool,
+) -> Option<Vec<VetStep>> {
+    let certifications = certifications
+        .iter()
+        .filter(|c| c.criteria.contains(criteria))
+        .collect::<Vec<_>>();
+
+    let mut queue = VecDeque::new();
+    let mut chains: HashMap<&str, Vec<VetStep>> = HashMap::new();
+    for &certification in &certifications {
+        let root = match &certification.step.kind {
+            VetStepKind::Full(root)
+            | VetStepKind::Wildcard(root, _)
+            | VetStepKind::Trusted(root, _) => root,
+            VetStepKind::Exemption(root) if with_exemptions => root,
+            _ => continue,
+        };
+        if !chains.contains_key(root.as_str()) {
+            chains.insert(root, vec![certification.step.clone()]);
+            queue.push_back(root.as_str());
+        }
+    }
+
+    while let Some(current) = queue.pop_front() {
+        if current == version {
+            return chains.remove(current);
+        }
+        for &certification in &certifications {
+            let VetStepKind::Delta(from, to) = &certification.step.kind else {
+                continue;
+            };
+            if from != current || chains.contains_key(to.as_str()) {
+                continue;
+            }
+            let mut chain = chains[current].clone();
+            chain.push(certification.step.clone());
+            chains.insert(to, chain);
+            queue.push_back(to);
+        }
+    }
+    None
+}
+
+fn normalize_version(version: &str) -> String {
+    let version = version.trim();
+    semver::Version::parse(version)
+        .map(|v| v.to_string())
+        .unwrap_or_else(|_| version.to_string())
+}
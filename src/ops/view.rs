@@ -3,169 +3,675 @@ use std::io::Write;
 
 use cargo::{
     core::{
-        dependency::DepKind, shell::Verbosity, Dependency, FeatureMap, Package, PackageId, SourceId,
+        dependency::DepKind,
+        resolver::CliFeatures,
+        shell::{Shell, Verbosity},
+        Dependency, FeatureMap, GitReference, Package, PackageId, SourceId, SourceKind,
     },
-    sources::IndexSummary,
-    util::interning::InternedString,
+    util::{human_readable_bytes, interning::InternedString},
     CargoResult, GlobalContext,
 };
 
-use super::style::{ERROR, HEADER, LITERAL, NOP, NOTE, WARN};
+use super::advisories::AdvisoryReport;
+use super::api::{CrateStats, ReverseDependencies};
+use super::compare::CrateComparison;
+use super::git::GitDetails;
+use super::info::{Owner, PackageInfo};
+use super::layout::{columns, stdout_width, text_width, wrap_list, wrap_text, MIN_WRAP_WIDTH};
+use super::provenance::Provenance;
+use super::registries::RegistryComparison;
+use super::search::SearchResults;
+use super::sections::Section;
+use super::style::{Link, Theme};
+use super::suggest::{NameConfusion, Popularity};
+use super::transitive::TransitiveSummary;
+use super::vet::{VetReport, VetStatus, VetStepKind};
 
-// Pretty print the package information.
+// Pretty print the package information, wrapped to the width of the terminal if known.
 pub(super) fn pretty_view(
-    package: &Package,
-    summaries: &[IndexSummary],
-    owners: &Option<Vec<String>>,
-    suggest_cargo_tree_command: bool,
+    info: &PackageInfo,
+    width: Option<usize>,
     gctx: &GlobalContext,
 ) -> CargoResult<()> {
+    let package = &info.package;
+    let summaries = &info.summaries;
+    let sections = &info.sections;
     let summary = package.manifest().summary();
     let package_id = summary.package_id();
     let metadata = package.manifest().metadata();
     let is_package_from_crates_io = summary.source_id().is_crates_io();
-    let header = HEADER;
-    let error = ERROR;
-    let warn = WARN;
-    let note = NOTE;
+    let theme = Theme::load(gctx)?;
+    let header = theme.header;
+    let error = theme.error;
+    let warn = theme.warn;
+    let note = theme.note;
 
     let mut shell = gctx.shell();
     let verbosity = shell.verbosity();
-    write!(shell.out(), "{header}{}{header:#}", package_id.name())?;
-    if !metadata.keywords.is_empty() {
-        let message = if is_package_from_crates_io {
+    if sections.contains(Section::Header) {
+        pretty_name(
+            package_id.name().as_str(),
+            &metadata.keywords,
+            is_package_from_crates_io,
+            &theme,
+            &mut shell,
+        )?;
+
+        let stdout = shell.out();
+        if let Some(ref description) = metadata.description {
+            pretty_description(description, width, stdout)?;
+        }
+        write!(
+            stdout,
+            "{header}version:{header:#} {}",
+            package_id.version()
+        )?;
+        // Add a warning message to stdout if the following conditions are met:
+        // 1. The package version is not the latest available version.
+        // 2. The package source is not crates.io.
+        match (
+            summaries.iter().max_by_key(|s| s.as_summary().version()),
+            is_package_from_crates_io,
+        ) {
+            (Some(latest), false) if latest.as_summary().version() != package_id.version() => {
+                write!(
+                    stdout,
+                    " {warn}(latest {} {warn:#}{note}from {}{note:#}{warn}){warn:#}",
+                    latest.as_summary().version(),
+                    pretty_package_source(summary.source_id(), gctx)
+                )?;
+            }
+            (Some(latest), true) if latest.as_summary().version() != package_id.version() => {
+                write!(
+                    stdout,
+                    " {warn}(latest {}){warn:#}",
+                    latest.as_summary().version(),
+                )?;
+            }
+            (_, false) => {
+                write!(
+                    stdout,
+                    " {note}(from {}){note:#}",
+                    pretty_package_source(summary.source_id(), gctx)
+                )?;
+            }
+            (_, true) => {}
+        }
+        writeln!(stdout)?;
+        if let Some(checksum) = &info.checksum {
+            writeln!(stdout, "{header}checksum:{header:#} {checksum}")?;
+        }
+        if let Some(git) = &info.git {
+            pretty_git(git, &theme, stdout)?;
+        }
+        writeln!(
+            stdout,
+            "{header}license:{header:#} {}",
             metadata
-                .keywords
+                .license
+                .clone()
+                .unwrap_or_else(|| format!("{error}unknown{error:#}"))
+        )?;
+        // TODO: color MSRV as a warning if newer than either the "workspace" MSRV or `rustc --version`
+        writeln!(
+            stdout,
+            "{header}rust-version:{header:#} {}",
+            metadata
+                .rust_version
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| format!("{warn}unknown{warn:#}"))
+        )?;
+    }
+
+    let stdout = shell.out();
+    if sections.contains(Section::Links) {
+        if let Some(ref link) = metadata.documentation.clone().or_else(|| {
+            is_package_from_crates_io.then(|| {
+                format!(
+                    "https://docs.rs/{name}/{version}",
+                    name = package_id.name(),
+                    version = package_id.version()
+                )
+            })
+        }) {
+            pretty_link("documentation", link, &theme, stdout)?;
+        }
+        if let Some(ref link) = metadata.homepage {
+            pretty_link("homepage", link, &theme, stdout)?;
+        }
+        if let Some(ref link) = metadata.repository {
+            pretty_link("repository", link, &theme, stdout)?;
+        }
+        // Only print the crates.io link if the package is from crates.io.
+        if is_package_from_crates_io {
+            let link = format!(
+                "https://crates.io/crates/{}/{}",
+                package_id.name(),
+                package_id.version()
+            );
+            pretty_link("crates.io", &link, &theme, stdout)?;
+        }
+    }
+
+    let activated = activated_features(&info.cli_features, summary.features());
+    let resolved_features = resolve_features(&activated, summary.features());
+    if sections.contains(Section::Features) {
+        pretty_features(
+            resolved_features.clone(),
+            summary.features(),
+            verbosity,
+            width,
+            &theme,
+            stdout,
+        )?;
+    }
+
+    if sections.contains(Section::Dependencies) {
+        // Dependencies are only shown in verbose mode, unless explicitly asked for.
+        let verbosity = if sections.is_selected(Section::Dependencies) {
+            Verbosity::Verbose
+        } else {
+            verbosity
+        };
+        pretty_deps(
+            package,
+            &resolved_features,
+            summary.features(),
+            verbosity,
+            width,
+            &theme,
+            stdout,
+            gctx,
+        )?;
+    }
+
+    if let Some(transitive) = sections.filter(Section::Transitive, &info.transitive) {
+        pretty_transitive(transitive, &theme, stdout)?;
+    }
+
+    if let Some(advisories) = sections.filter(Section::Advisories, &info.advisories) {
+        pretty_advisories(advisories, &theme, stdout)?;
+    }
+
+    if let Some(audits) = sections.filter(Section::Audits, &info.audits) {
+        pretty_audits(audits, &theme, stdout)?;
+    }
+
+    if let Some(provenance) = sections.filter(Section::Provenance, &info.provenance) {
+        pretty_provenance(provenance, &theme, stdout)?;
+    }
+
+    if let Some(owners) = sections.filter(Section::Owners, &info.owners) {
+        pretty_owners(owners, &theme, stdout)?;
+    }
+
+    if let Some(stats) = sections.filter(Section::Stats, &info.stats) {
+        pretty_stats(stats, verbosity, &theme, stdout)?;
+    }
+
+    if let Some(reverse_dependencies) =
+        sections.filter(Section::ReverseDeps, &info.reverse_dependencies)
+    {
+        pretty_reverse_dependencies(reverse_dependencies, &theme, stdout)?;
+    }
+
+    if info.suggest_cargo_tree_command && sections.contains(Section::Notes) {
+        suggest_cargo_tree(package_id, &theme, stdout)?;
+    }
+
+    Ok(())
+}
+
+// Print the name of the package with its keywords, linked to crates.io if possible.
+fn pretty_name(
+    name: &str,
+    keywords: &[String],
+    is_package_from_crates_io: bool,
+    theme: &Theme,
+    shell: &mut Shell,
+) -> CargoResult<()> {
+    let header = theme.header;
+    let note = theme.note;
+
+    let link = if is_package_from_crates_io {
+        theme.link(format!("https://crates.io/crates/{name}"))
+    } else {
+        Link::default()
+    };
+    write!(shell.out(), "{link}{header}{name}{header:#}{link:#}")?;
+    if !keywords.is_empty() {
+        let message = if is_package_from_crates_io {
+            keywords
                 .iter()
                 .map(|keyword| {
-                    let link = shell.out_hyperlink(format!("https://crates.io/keywords/{keyword}"));
+                    let link = theme.link(format!("https://crates.io/keywords/{keyword}"));
                     format!("{link}#{keyword}{link:#}")
                 })
                 .collect::<Vec<_>>()
                 .join(" ")
         } else {
-            format!("#{}", metadata.keywords.join(" #"))
+            format!("#{}", keywords.join(" #"))
         };
         write!(shell.out(), " {note}{message}{note:#}")?;
     }
+    writeln!(shell.out())?;
 
-    let stdout = shell.out();
-    writeln!(stdout)?;
-    if let Some(ref description) = metadata.description {
-        writeln!(stdout, "{}", description.trim_end())?;
+    Ok(())
+}
+
+// Print a field with a URL as value, as a hyperlink if supported.
+fn pretty_link(field: &str, url: &str, theme: &Theme, stdout: &mut dyn Write) -> CargoResult<()> {
+    let header = theme.header;
+    let link = theme.link(url);
+
+    writeln!(stdout, "{header}{field}:{header:#} {link}{url}{link:#}")?;
+
+    Ok(())
+}
+
+// Print the description, wrapped to the width of the terminal if known.
+fn pretty_description(
+    description: &str,
+    width: Option<usize>,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let description = description.trim_end();
+    match width {
+        Some(width) => {
+            for line in wrap_text(description, width) {
+                writeln!(stdout, "{line}")?;
+            }
+        }
+        None => writeln!(stdout, "{description}")?,
     }
-    write!(
-        stdout,
-        "{header}version:{header:#} {}",
-        package_id.version()
-    )?;
-    // Add a warning message to stdout if the following conditions are met:
-    // 1. The package version is not the latest available version.
-    // 2. The package source is not crates.io.
-    match (
-        summaries.iter().max_by_key(|s| s.as_summary().version()),
-        is_package_from_crates_io,
-    ) {
-        (Some(latest), false) if latest.as_summary().version() != package_id.version() => {
-            write!(
-                stdout,
-                " {warn}(latest {} {warn:#}{note}from {}{note:#}{warn}){warn:#}",
-                latest.as_summary().version(),
-                pretty_source(summary.source_id(), gctx)
-            )?;
+    Ok(())
+}
+
+// Pretty print the search results as cards, numbered to pick one of them.
+pub(super) fn pretty_search(results: &SearchResults, gctx: &GlobalContext) -> CargoResult<()> {
+    let theme = Theme::load(gctx)?;
+    let header = theme.header;
+    let warn = theme.warn;
+    let literal = theme.literal;
+
+    let width = stdout_width(gctx);
+    let mut shell = gctx.shell();
+    if results.hits.is_empty() {
+        note(
+            format_args!("no crates match `{}`", results.query),
+            &theme,
+            shell.out(),
+        )?;
+        return Ok(());
+    }
+
+    for (i, hit) in results.hits.iter().enumerate() {
+        if i > 0 {
+            writeln!(shell.out())?;
         }
-        (Some(latest), true) if latest.as_summary().version() != package_id.version() => {
-            write!(
-                stdout,
-                " {warn}(latest {}){warn:#}",
-                latest.as_summary().version(),
-            )?;
+        write!(shell.out(), "{literal}[{}]{literal:#} ", i + 1)?;
+        pretty_name(
+            &hit.name,
+            &hit.keywords,
+            results.is_crates_io,
+            &theme,
+            &mut shell,
+        )?;
+        let stdout = shell.out();
+        if let Some(description) = &hit.description {
+            pretty_description(description, width, stdout)?;
         }
-        (_, false) => {
-            write!(
-                stdout,
-                " {note}(from {}){note:#}",
-                pretty_source(summary.source_id(), gctx)
-            )?;
+        writeln!(stdout, "{header}version:{header:#} {}", hit.version)?;
+        match &hit.rust_version {
+            Some(rust_version) if hit.is_msrv_compatible => {
+                writeln!(stdout, "{header}rust-version:{header:#} {rust_version}")?;
+            }
+            Some(rust_version) => {
+                writeln!(
+                    stdout,
+                    "{header}rust-version:{header:#} {rust_version} {warn}(incompatible with {}){warn:#}",
+                    results.rustc_version
+                )?;
+            }
+            None => {
+                writeln!(
+                    stdout,
+                    "{header}rust-version:{header:#} {warn}unknown{warn:#}"
+                )?;
+            }
         }
-        (_, true) => {}
     }
+
+    let stdout = shell.out();
     writeln!(stdout)?;
-    writeln!(
-        stdout,
-        "{header}license:{header:#} {}",
-        metadata
-            .license
-            .clone()
-            .unwrap_or_else(|| format!("{error}unknown{error:#}"))
-    )?;
-    // TODO: color MSRV as a warning if newer than either the "workspace" MSRV or `rustc --version`
-    writeln!(
-        stdout,
-        "{header}rust-version:{header:#} {}",
-        metadata
-            .rust_version
-            .as_ref()
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| format!("{warn}unknown{warn:#}"))
-    )?;
-    if let Some(ref link) = metadata.documentation.clone().or_else(|| {
-        is_package_from_crates_io.then(|| {
-            format!(
-                "https://docs.rs/{name}/{version}",
-                name = package_id.name(),
-                version = package_id.version()
-            )
-        })
-    }) {
-        writeln!(stdout, "{header}documentation:{header:#} {link}")?;
+    let shown = results.hits.len();
+    if (shown as u64) < u64::from(results.total) {
+        note(
+            format_args!(
+                "showing {shown} of {} crates matching `{}`",
+                results.total, results.query
+            ),
+            &theme,
+            stdout,
+        )?;
     }
-    if let Some(ref link) = metadata.homepage {
-        writeln!(stdout, "{header}homepage:{header:#} {link}")?;
+    note(
+        format_args!(
+            "to see the full info of a crate, run `{literal}cargo info --search {} --pick <N>{literal:#}`",
+            results.query
+        ),
+        &theme,
+        stdout,
+    )
+}
+
+// Pretty print what each registry carries of the crate.
+pub(super) fn pretty_registries(
+    comparison: &RegistryComparison,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let theme = Theme::load(gctx)?;
+    let header = theme.header;
+    let error = theme.error;
+    let warn = theme.warn;
+
+    let mut shell = gctx.shell();
+    let stdout = shell.out();
+    writeln!(stdout, "{header}{}{header:#}", comparison.name)?;
+    writeln!(stdout, "{header}registries:{header:#}")?;
+    let margin = comparison
+        .registries
+        .iter()
+        .map(|r| r.registry.len())
+        .max()
+        .unwrap_or_default();
+    for listing in &comparison.registries {
+        let registry = &listing.registry;
+        match (&listing.error, &listing.latest) {
+            (Some(err), _) => {
+                writeln!(
+                    stdout,
+                    "  {registry: <margin$}  {error}error: {err}{error:#}"
+                )?;
+            }
+            (None, Some(latest)) => {
+                let plural = if listing.versions == 1 { "" } else { "s" };
+                writeln!(
+                    stdout,
+                    "  {registry: <margin$}  {latest} ({} version{plural})",
+                    listing.versions
+                )?;
+            }
+            (None, None) if listing.versions > 0 => {
+                writeln!(
+                    stdout,
+                    "  {registry: <margin$}  {warn}all versions yanked{warn:#}"
+                )?;
+            }
+            (None, None) => {
+                writeln!(stdout, "  {registry: <margin$}  not found")?;
+            }
+        }
     }
-    if let Some(ref link) = metadata.repository {
-        writeln!(stdout, "{header}repository:{header:#} {link}")?;
+
+    if comparison.shared_versions == 0 {
+        return Ok(());
     }
-    // Only print the crates.io link if the package is from crates.io.
-    if is_package_from_crates_io {
+    if comparison.mismatches.is_empty() {
+        let plural = if comparison.shared_versions == 1 {
+            ""
+        } else {
+            "s"
+        };
         writeln!(
             stdout,
-            "{header}crates.io:{header:#} https://crates.io/crates/{}/{}",
-            package_id.name(),
-            package_id.version()
+            "{header}checksums:{header:#} match for {} shared version{plural}",
+            comparison.shared_versions
         )?;
+        return Ok(());
     }
-
-    let activated = &[InternedString::new("default")];
-    let resolved_features = resolve_features(activated, summary.features());
-    pretty_features(
-        resolved_features.clone(),
-        summary.features(),
-        verbosity,
+    writeln!(
         stdout,
+        "{header}checksums:{header:#} {error}{} of {} shared versions differ{error:#}",
+        comparison.mismatches.len(),
+        comparison.shared_versions
     )?;
+    for mismatch in &comparison.mismatches {
+        writeln!(stdout, "  {}:", mismatch.version)?;
+        for (registry, checksum) in &mismatch.checksums {
+            writeln!(stdout, "    {registry: <margin$}  {checksum}")?;
+        }
+    }
 
-    pretty_deps(
-        package,
-        &resolved_features,
-        summary.features(),
-        verbosity,
-        stdout,
-        gctx,
-    )?;
+    Ok(())
+}
+
+// Pretty print the crates side by side, one column per crate and one row per compared field.
+pub(super) fn pretty_comparison(
+    comparison: &CrateComparison,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let theme = Theme::load(gctx)?;
+    let header = theme.header;
+    let plain = anstyle::Style::new();
 
-    if let Some(owners) = owners {
-        pretty_owners(owners, stdout)?;
+    // Each cell is made of styled parts, the columns are aligned on their plain text.
+    // The dependencies that are not downloaded are not inspected, so their count is partial.
+    let yes_no = |own: bool, transitive: usize, not_downloaded: usize| {
+        let own = if own { "yes" } else { "no" };
+        match (transitive, not_downloaded) {
+            (0, 0) => vec![(own.to_string(), plain)],
+            (_, 0) => vec![(format!("{own}, {transitive} in dependencies"), plain)],
+            _ => vec![
+                (format!("{own}, {transitive} in dependencies "), plain),
+                ("(partial)".to_string(), theme.warn),
+            ],
+        }
+    };
+    let mut rows: Vec<(&str, Vec<Vec<(String, anstyle::Style)>>)> = vec![
+        (
+            "version",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| match &krate.latest {
+                    Some(latest) if *latest != krate.version => {
+                        vec![(format!("{} (latest {latest})", krate.version), plain)]
+                    }
+                    _ => vec![(krate.version.to_string(), plain)],
+                })
+                .collect(),
+        ),
+        (
+            "rust-version",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| match &krate.rust_version {
+                    Some(rust_version) if krate.is_msrv_compatible => {
+                        vec![(rust_version.clone(), plain)]
+                    }
+                    Some(rust_version) => vec![
+                        (format!("{rust_version} "), plain),
+                        (
+                            format!("(incompatible with {})", comparison.rustc_version),
+                            theme.warn,
+                        ),
+                    ],
+                    None => vec![("unknown".to_string(), theme.warn)],
+                })
+                .collect(),
+        ),
+        (
+            "license",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| match &krate.license {
+                    Some(license) => vec![(license.clone(), plain)],
+                    None => vec![("unknown".to_string(), theme.error)],
+                })
+                .collect(),
+        ),
+        (
+            "default features",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| vec![(krate.default_features.to_string(), plain)])
+                .collect(),
+        ),
+        (
+            "dependencies",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| {
+                    vec![(
+                        format!(
+                            "{} direct, {} transitive",
+                            krate.direct_dependencies, krate.transitive_dependencies
+                        ),
+                        plain,
+                    )]
+                })
+                .collect(),
+        ),
+        (
+            "build script",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| {
+                    yes_no(
+                        krate.build_script,
+                        krate.transitive_build_scripts,
+                        krate.transitive_not_downloaded,
+                    )
+                })
+                .collect(),
+        ),
+        (
+            "proc-macro",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| {
+                    yes_no(
+                        krate.proc_macro,
+                        krate.transitive_proc_macros,
+                        krate.transitive_not_downloaded,
+                    )
+                })
+                .collect(),
+        ),
+        (
+            "owners",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| match krate.owners {
+                    Some(owners) => vec![(owners.to_string(), plain)],
+                    None => vec![("unknown".to_string(), theme.disabled)],
+                })
+                .collect(),
+        ),
+    ];
+    if comparison.stats {
+        rows.push((
+            "last publish",
+            comparison
+                .crates
+                .iter()
+                .map(|krate| match &krate.last_publish {
+                    Some(date) => vec![(date.clone(), plain)],
+                    None => vec![("unknown".to_string(), theme.disabled)],
+                })
+                .collect(),
+        ));
     }
 
-    if suggest_cargo_tree_command {
-        suggest_cargo_tree(package_id, stdout)?;
+    let cell_width = |cell: &[(String, anstyle::Style)]| -> usize {
+        cell.iter().map(|(text, _)| text_width(text)).sum()
+    };
+    let margin = rows
+        .iter()
+        .map(|(label, _)| text_width(label) + 1)
+        .max()
+        .unwrap_or_default();
+    let widths = comparison
+        .crates
+        .iter()
+        .enumerate()
+        .map(|(i, krate)| {
+            rows.iter()
+                .map(|(_, cells)| cell_width(&cells[i]))
+                .chain([text_width(&krate.name)])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut shell = gctx.shell();
+    let stdout = shell.out();
+    // The padding of a cell is written before the next one, so that lines don't end with spaces.
+    let mut padding = margin;
+    for (krate, width) in comparison.crates.iter().zip(&widths) {
+        write!(stdout, "{:padding$}  {header}{}{header:#}", "", krate.name)?;
+        padding = width - text_width(&krate.name);
+    }
+    writeln!(stdout)?;
+    for (label, cells) in &rows {
+        write!(stdout, "{header}{label}:{header:#}")?;
+        let mut padding = margin - text_width(label) - 1;
+        for (cell, width) in cells.iter().zip(&widths) {
+            write!(stdout, "{:padding$}  ", "")?;
+            for (text, style) in cell {
+                write!(stdout, "{style}{text}{style:#}")?;
+            }
+            padding = width - cell_width(cell);
+        }
+        writeln!(stdout)?;
+    }
+    let not_downloaded = comparison
+        .crates
+        .iter()
+        .map(|krate| krate.transitive_not_downloaded)
+        .sum::<usize>();
+    if not_downloaded > 0 {
+        note(
+            format_args!(
+                "{not_downloaded} of the dependencies are not downloaded yet, so the build scripts \
+                 and proc-macros in dependencies are partial"
+            ),
+            &theme,
+            stdout,
+        )?;
     }
 
     Ok(())
 }
 
-fn pretty_source(source: SourceId, ctx: &GlobalContext) -> String {
+pub(super) fn pretty_source(source: SourceId, ctx: &GlobalContext) -> String {
+    // Offline sources are shown by their path, with what kind of source they are.
+    let offline_source = match source.kind() {
+        SourceKind::Directory => Some("vendored directory"),
+        SourceKind::LocalRegistry => Some("local registry"),
+        _ => None,
+    };
+    if let Some(kind) = offline_source {
+        if let Some(relpath) = source
+            .url()
+            .to_file_path()
+            .ok()
+            .and_then(|path| pathdiff::diff_paths(path, ctx.cwd()))
+        {
+            let path = std::path::Path::new(".").join(relpath);
+            return format!("{kind} `{}`", path.display());
+        }
+    }
+
     if let Some(relpath) = source
         .local_path()
         .and_then(|path| pathdiff::diff_paths(path, ctx.cwd()))
@@ -177,11 +683,56 @@ fn pretty_source(source: SourceId, ctx: &GlobalContext) -> String {
     }
 }
 
+// Like `pretty_source`, for the source of the package itself, whose git reference and locked
+// commit are shown with the git details.
+pub(super) fn pretty_package_source(source: SourceId, ctx: &GlobalContext) -> String {
+    if source.is_git() {
+        return source.url().to_string();
+    }
+    pretty_source(source, ctx)
+}
+
+fn pretty_git(git: &GitDetails, theme: &Theme, stdout: &mut dyn Write) -> CargoResult<()> {
+    let header = theme.header;
+    let warn = theme.warn;
+
+    writeln!(stdout, "{header}git:{header:#}")?;
+    let reference = match &git.reference {
+        GitReference::Branch(branch) => format!("branch `{branch}`"),
+        GitReference::Tag(tag) => format!("tag `{tag}`"),
+        GitReference::Rev(rev) => format!("rev `{rev}`"),
+        GitReference::DefaultBranch => "default branch".to_string(),
+    };
+    writeln!(stdout, "  reference: {reference}")?;
+    if let Some(locked) = &git.locked {
+        write!(stdout, "  commit: {locked}")?;
+        if let Some(commit) = &git.commit {
+            write!(stdout, " ({}) {}", commit.date, commit.summary)?;
+        }
+        writeln!(stdout)?;
+    }
+    match git.behind {
+        Some(0) => writeln!(stdout, "  up to date with the {reference}")?,
+        Some(behind) => {
+            let plural = if behind == 1 { "" } else { "s" };
+            writeln!(
+                stdout,
+                "  {warn}{behind} commit{plural} behind the {reference}{warn:#}"
+            )?;
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
 fn pretty_deps(
     package: &Package,
     resolved_features: &[(InternedString, FeatureStatus)],
     features: &FeatureMap,
     verbosity: Verbosity,
+    width: Option<usize>,
+    theme: &Theme,
     stdout: &mut dyn Write,
     gctx: &GlobalContext,
 ) -> CargoResult<()> {
@@ -192,7 +743,7 @@ fn pretty_deps(
         Verbosity::Verbose => {}
     }
 
-    let header = HEADER;
+    let header = theme.header;
 
     let dependencies = package
         .dependencies()
@@ -201,7 +752,15 @@ fn pretty_deps(
         .collect::<Vec<_>>();
     if !dependencies.is_empty() {
         writeln!(stdout, "{header}dependencies:{header:#}")?;
-        print_deps(dependencies, resolved_features, features, stdout, gctx)?;
+        print_deps(
+            dependencies,
+            resolved_features,
+            features,
+            width,
+            theme,
+            stdout,
+            gctx,
+        )?;
     }
 
     let build_dependencies = package
@@ -215,6 +774,8 @@ fn pretty_deps(
             build_dependencies,
             resolved_features,
             features,
+            width,
+            theme,
             stdout,
             gctx,
         )?;
@@ -227,78 +788,127 @@ fn print_deps(
     dependencies: Vec<&Dependency>,
     resolved_features: &[(InternedString, FeatureStatus)],
     features: &FeatureMap,
+    width: Option<usize>,
+    theme: &Theme,
     stdout: &mut dyn Write,
     gctx: &GlobalContext,
 ) -> Result<(), anyhow::Error> {
-    let enabled_by_user = HEADER;
-    let enabled = NOP;
-    let disabled = anstyle::Style::new() | anstyle::Effects::DIMMED;
+    let enabled_by_user = theme.header;
+    let enabled = anstyle::Style::new();
+    let disabled = theme.disabled;
 
     let mut dependencies = dependencies
         .into_iter()
         .map(|dependency| {
-            let status = if !dependency.is_optional() {
-                FeatureStatus::EnabledByUser
-            } else if resolved_features
-                .iter()
-                .filter(|(_, s)| !s.is_disabled())
-                .filter_map(|(n, _)| features.get(n))
-                .flatten()
-                .filter_map(|f| match f {
-                    cargo::core::FeatureValue::Feature(_) => None,
-                    cargo::core::FeatureValue::Dep { dep_name } => Some(dep_name),
-                    cargo::core::FeatureValue::DepFeature { dep_name, weak, .. } if *weak => {
-                        Some(dep_name)
-                    }
-                    cargo::core::FeatureValue::DepFeature { .. } => None,
-                })
-                .any(|dep_name| *dep_name == dependency.name_in_toml())
-            {
-                FeatureStatus::Enabled
-            } else {
-                FeatureStatus::Disabled
-            };
+            let status = dependency_status(dependency, resolved_features, features);
             (dependency, status)
         })
         .collect::<Vec<_>>();
     dependencies.sort_by_key(|(d, s)| (*s, d.package_name()));
-    for (dependency, status) in dependencies {
-        // 1. Only print the version requirement if it is a registry dependency.
-        // 2. Only print the source if it is not a registry dependency.
-        // For example: `bar (./crates/bar)` or `bar@=1.2.3`.
-        let (req, source) = if dependency.source_id().is_registry() {
-            (
-                format!("@{}", pretty_req(dependency.version_req())),
-                String::new(),
-            )
-        } else {
+    let entries = dependencies
+        .into_iter()
+        .map(|(dependency, status)| {
+            // 1. Only print the version requirement if it is a registry dependency.
+            // 2. Only print the source if it is not a registry dependency.
+            // For example: `bar (./crates/bar)` or `bar@=1.2.3`.
+            let (req, source) = if dependency.source_id().is_registry() {
+                (
+                    format!("@{}", pretty_req(dependency.version_req())),
+                    String::new(),
+                )
+            } else {
+                (
+                    String::new(),
+                    format!(" ({})", pretty_source(dependency.source_id(), gctx)),
+                )
+            };
+            // Link to the page of the crate on crates.io, or to the git repository. Other
+            // registries have no known web page, so their dependencies are not linked.
+            let source_id = dependency.source_id();
+            let url = if source_id.is_crates_io() {
+                Some(format!(
+                    "https://crates.io/crates/{}",
+                    dependency.package_name()
+                ))
+            } else if source_id.is_git() {
+                Some(source_id.url().to_string())
+            } else {
+                None
+            };
             (
-                String::new(),
-                format!(" ({})", pretty_source(dependency.source_id(), gctx)),
+                status,
+                format!("{}{req}{source}", dependency.package_name()),
+                url,
             )
-        };
+        })
+        .collect::<Vec<_>>();
 
-        if status == FeatureStatus::EnabledByUser {
-            write!(stdout, " {enabled_by_user}+{enabled_by_user:#}")?;
-        } else {
-            write!(stdout, "  ")?;
+    // Lay out long lists in columns if the width of the terminal is known.
+    const MAX_SINGLE_COLUMN_DEPS: usize = 10;
+    let widths = entries
+        .iter()
+        .map(|(_, text, _)| 2 + text_width(text))
+        .collect::<Vec<_>>();
+    let (rows, column_width) = width
+        .filter(|_| entries.len() > MAX_SINGLE_COLUMN_DEPS)
+        .and_then(|width| columns(&widths, width))
+        .unwrap_or_else(|| ((0..entries.len()).map(|i| vec![i]).collect(), 0));
+    for row in rows {
+        for (column, &i) in row.iter().enumerate() {
+            let (status, text, url) = &entries[i];
+            if column > 0 {
+                let padding = column_width - widths[row[column - 1]];
+                write!(stdout, "{:padding$}", "")?;
+            }
+            if *status == FeatureStatus::EnabledByUser {
+                write!(stdout, " {enabled_by_user}+{enabled_by_user:#}")?;
+            } else {
+                write!(stdout, "  ")?;
+            }
+            let style = match status {
+                FeatureStatus::EnabledByUser | FeatureStatus::Enabled => enabled,
+                FeatureStatus::Disabled => disabled,
+            };
+            let link = match url {
+                Some(url) => theme.link(url.as_str()),
+                None => Link::default(),
+            };
+            write!(stdout, "{link}{style}{text}{style:#}{link:#}")?;
         }
-        let style = match status {
-            FeatureStatus::EnabledByUser | FeatureStatus::Enabled => enabled,
-            FeatureStatus::Disabled => disabled,
-        };
-        writeln!(
-            stdout,
-            "{style}{}{}{}{style:#}",
-            dependency.package_name(),
-            req,
-            source
-        )?;
+        writeln!(stdout)?;
     }
     Ok(())
 }
 
-fn pretty_req(req: &cargo::util::OptVersionReq) -> String {
+// Whether the dependency is always enabled, enabled by the resolved features, or disabled.
+pub(super) fn dependency_status(
+    dependency: &Dependency,
+    resolved_features: &[(InternedString, FeatureStatus)],
+    features: &FeatureMap,
+) -> FeatureStatus {
+    if !dependency.is_optional() {
+        return FeatureStatus::EnabledByUser;
+    }
+    let enabled = resolved_features
+        .iter()
+        .filter(|(_, s)| !s.is_disabled())
+        .filter_map(|(n, _)| features.get(n))
+        .flatten()
+        .filter_map(|f| match f {
+            cargo::core::FeatureValue::Feature(_) => None,
+            cargo::core::FeatureValue::Dep { dep_name } => Some(dep_name),
+            cargo::core::FeatureValue::DepFeature { dep_name, weak, .. } if *weak => Some(dep_name),
+            cargo::core::FeatureValue::DepFeature { .. } => None,
+        })
+        .any(|dep_name| *dep_name == dependency.name_in_toml());
+    if enabled {
+        FeatureStatus::Enabled
+    } else {
+        FeatureStatus::Disabled
+    }
+}
+
+pub(super) fn pretty_req(req: &cargo::util::OptVersionReq) -> String {
     let mut rendered = req.to_string();
     let strip_prefix = match req {
         cargo::util::OptVersionReq::Any => false,
@@ -320,13 +930,15 @@ fn pretty_features(
     resolved_features: Vec<(InternedString, FeatureStatus)>,
     features: &FeatureMap,
     verbosity: Verbosity,
+    width: Option<usize>,
+    theme: &Theme,
     stdout: &mut dyn Write,
 ) -> CargoResult<()> {
-    let header = HEADER;
-    let enabled_by_user = HEADER;
-    let enabled = NOP;
-    let disabled = anstyle::Style::new() | anstyle::Effects::DIMMED;
-    let summary = anstyle::Style::new() | anstyle::Effects::ITALIC;
+    let header = theme.header;
+    let enabled_by_user = theme.header;
+    let enabled = anstyle::Style::new();
+    let disabled = theme.disabled;
+    let summary = theme.summary;
 
     // If there are no features, return early.
     let margin = features
@@ -374,15 +986,42 @@ fn pretty_features(
             FeatureStatus::EnabledByUser | FeatureStatus::Enabled => enabled,
             FeatureStatus::Disabled => disabled,
         };
-        writeln!(
-            stdout,
-            "{style}{current: <margin$}{style:#} = [{features}]",
-            features = current_activated
+        let values = current_activated
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        let values = values.iter().map(String::as_str).collect::<Vec<_>>();
+        // Wrap long lists under a hanging indent, aligned after the bracket if there is room.
+        let indent = 2 + margin + " = [".len();
+        let (lines, rest_indent) = match width {
+            Some(width) => {
+                let rest_indent = if width.saturating_sub(indent) >= MIN_WRAP_WIDTH {
+                    indent
+                } else {
+                    4
+                };
+                let lines = wrap_list(
+                    &values,
+                    width.saturating_sub(indent),
+                    width.saturating_sub(rest_indent),
+                );
+                (lines, rest_indent)
+            }
+            None => (vec![values], indent),
+        };
+        write!(stdout, "{style}{current: <margin$}{style:#} = [")?;
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                write!(stdout, "{:rest_indent$}", "")?;
+            }
+            let line = line
                 .iter()
                 .map(|s| format!("{style}{s}{style:#}"))
                 .collect::<Vec<String>>()
-                .join(", ")
-        )?;
+                .join(", ");
+            let end = if i + 1 == lines.len() { "]" } else { "," };
+            writeln!(stdout, "{line}{end}")?;
+        }
     }
     if !show_activated {
         writeln!(
@@ -400,33 +1039,457 @@ fn pretty_features(
     Ok(())
 }
 
-fn pretty_owners(owners: &Vec<String>, stdout: &mut dyn Write) -> CargoResult<()> {
-    let header = HEADER;
+fn pretty_transitive(
+    transitive: &TransitiveSummary,
+    theme: &Theme,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let header = theme.header;
+    let warn = theme.warn;
+
+    writeln!(stdout, "{header}transitive dependencies:{header:#}")?;
+    let (size, unit) = human_readable_bytes(transitive.download_size);
+    let crates = format!(
+        "{} crate{}",
+        transitive.crates,
+        if transitive.crates == 1 { "" } else { "s" }
+    );
+    // The size is only known for the downloaded crates, so don't pass a partial sum as the total.
+    let downloaded = transitive.crates - transitive.not_downloaded;
+    if transitive.not_downloaded == 0 {
+        writeln!(stdout, "  {crates}, {size:.1}{unit} to download")?;
+    } else if downloaded == 0 {
+        writeln!(stdout, "  {crates}")?;
+    } else {
+        writeln!(
+            stdout,
+            "  {crates}, {size:.1}{unit} for the {downloaded} downloaded ones"
+        )?;
+    }
+    if transitive.crates == 0 {
+        return Ok(());
+    }
+    if !transitive.licenses.is_empty() {
+        writeln!(
+            stdout,
+            "  licenses: {}",
+            transitive
+                .licenses
+                .iter()
+                .map(|(license, count)| format!("{license} ({count})"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+    }
+    match &transitive.max_rust_version {
+        Some((rust_version, package_id)) => writeln!(
+            stdout,
+            "  rust-version: {rust_version} ({}@{})",
+            package_id.name(),
+            package_id.version()
+        )?,
+        None => writeln!(stdout, "  rust-version: {warn}unknown{warn:#}")?,
+    }
+    if !transitive.build_scripts.is_empty() {
+        writeln!(
+            stdout,
+            "  build scripts: {}",
+            pretty_names(&transitive.build_scripts)
+        )?;
+    }
+    if !transitive.proc_macros.is_empty() {
+        writeln!(
+            stdout,
+            "  proc-macros: {}",
+            pretty_names(&transitive.proc_macros)
+        )?;
+    }
+    if transitive.not_downloaded > 0 {
+        note(
+            format_args!(
+                "{} of {crates} not downloaded, totals are partial",
+                transitive.not_downloaded
+            ),
+            theme,
+            stdout,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn pretty_advisories(
+    advisories: &AdvisoryReport,
+    theme: &Theme,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let header = theme.header;
+    let error = theme.error;
+    let warn = theme.warn;
+
+    if advisories.advisories.is_empty() {
+        writeln!(stdout, "{header}advisories:{header:#} none")?;
+        return Ok(());
+    }
+
+    writeln!(stdout, "{header}advisories:{header:#}")?;
+    for advisory in &advisories.advisories {
+        let kind = match (&advisory.informational, advisory.severity) {
+            (Some(informational), _) => format!(" {warn}({informational}){warn:#}"),
+            (None, Some(severity)) => format!(" {error}({severity}){error:#}"),
+            (None, None) => String::new(),
+        };
+        writeln!(stdout, "  {}{kind}: {}", advisory.id, advisory.title)?;
+        if !advisory.patched.is_empty() {
+            writeln!(stdout, "    patched: {}", advisory.patched.join(", "))?;
+        }
+    }
+    if let Some(recommended) = advisories.recommended {
+        let literal = theme.literal;
+        note(
+            format_args!(
+                "the nearest unaffected version is {literal}{}@{}{literal:#}",
+                recommended.name(),
+                recommended.version()
+            ),
+            theme,
+            stdout,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn pretty_audits(audits: &VetReport, theme: &Theme, stdout: &mut dyn Write) -> CargoResult<()> {
+    let header = theme.header;
+    let warn = theme.warn;
+    let note = theme.note;
+
+    writeln!(stdout, "{header}audits:{header:#}")?;
+    for criteria in &audits.criteria {
+        let status = match criteria.status {
+            VetStatus::Audited => "audited".to_string(),
+            VetStatus::Exempted => format!("{note}exempted{note:#}"),
+            VetStatus::Unaudited => format!("{warn}unaudited{warn:#}"),
+        };
+        write!(stdout, "  {}: {status}", criteria.name)?;
+        if !criteria.chain.is_empty() {
+            let chain = criteria
+                .chain
+                .iter()
+                .map(|step| {
+                    let kind = match &step.kind {
+                        VetStepKind::Full(version) => version.clone(),
+                        VetStepKind::Delta(from, to) => format!("{from} -> {to}"),
+                        VetStepKind::Exemption(version) => format!("exemption {version}"),
+                        VetStepKind::Wildcard(version, publisher) => {
+                            format!("{version} by {publisher}, wildcard audit")
+                        }
+                        VetStepKind::Trusted(version, publisher) => {
+                            format!("{version} by trusted {publisher}")
+                        }
+                    };
+                    match &step.import {
+                        Some(peer) => format!("{kind} from {peer}"),
+                        None => kind,
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(stdout, " ({chain})")?;
+        }
+        writeln!(stdout)?;
+    }
+    if audits.unknown_publisher {
+        note(
+            "wildcard audits and trusted publishers are not checked, the publisher of this \
+             version is not in `imports.lock`, run `cargo vet` to record it",
+            theme,
+            stdout,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn pretty_provenance(
+    provenance: &Provenance,
+    theme: &Theme,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let header = theme.header;
+    let error = theme.error;
+    let warn = theme.warn;
+    let note = theme.note;
+
+    writeln!(stdout, "{header}provenance:{header:#}")?;
+    match (&provenance.checksum, &provenance.actual_checksum) {
+        (Some(expected), Some(_)) if provenance.is_mismatch() => {
+            writeln!(stdout, "  checksum: {expected} {error}(mismatch){error:#}")?;
+        }
+        (Some(expected), Some(_)) => {
+            writeln!(stdout, "  checksum: {expected} {note}(verified){note:#}")?;
+        }
+        (Some(expected), None) => {
+            writeln!(
+                stdout,
+                "  checksum: {expected} {warn}(not verified, the `.crate` file is not cached){warn:#}"
+            )?;
+        }
+        (None, _) => {
+            writeln!(stdout, "  checksum: {warn}unknown{warn:#}")?;
+        }
+    }
+    if let Some(actual) = provenance
+        .actual_checksum
+        .as_ref()
+        .filter(|_| provenance.is_mismatch())
+    {
+        writeln!(stdout, "  cached checksum: {error}{actual}{error:#}")?;
+    }
+    if let Some(template) = &provenance.download_template {
+        writeln!(stdout, "  download: {template}")?;
+    }
+    if let Some(crate_file) = &provenance.crate_file {
+        writeln!(stdout, "  crate file: {}", crate_file.display())?;
+    }
+    writeln!(stdout, "  sources: {}", provenance.source_dir.display())?;
+
+    Ok(())
+}
+
+fn pretty_names(package_ids: &[PackageId]) -> String {
+    package_ids
+        .iter()
+        .map(|id| id.name().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn pretty_owners(owners: &[Owner], theme: &Theme, stdout: &mut dyn Write) -> CargoResult<()> {
+    let header = theme.header;
 
     if !owners.is_empty() {
         writeln!(stdout, "{header}owners:{header:#}",)?;
         for owner in owners {
-            writeln!(stdout, "  {}", owner)?;
+            // Link to the profile of the owner.
+            let link = match &owner.url {
+                Some(url) => theme.link(url.as_str()),
+                None => Link::default(),
+            };
+            writeln!(stdout, "  {link}{}{link:#}", owner.name)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn pretty_stats(
+    stats: &CrateStats,
+    verbosity: Verbosity,
+    theme: &Theme,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let header = theme.header;
+    let warn = theme.warn;
+    let summary = theme.summary;
+
+    write!(
+        stdout,
+        "{header}downloads:{header:#} {}",
+        stats.krate.downloads
+    )?;
+    if let Some(recent_downloads) = stats.krate.recent_downloads {
+        write!(stdout, " ({recent_downloads} recent)")?;
+    }
+    writeln!(stdout)?;
+    writeln!(
+        stdout,
+        "{header}created:{header:#} {}",
+        pretty_date(&stats.krate.created_at)
+    )?;
+    writeln!(
+        stdout,
+        "{header}updated:{header:#} {}",
+        pretty_date(&stats.krate.updated_at)
+    )?;
+
+    let versions = stats.versions.as_deref().unwrap_or_default();
+    if versions.is_empty() {
+        return Ok(());
+    }
+    const MAX_VERSION_PRINTS: usize = 5;
+    let show_all = match verbosity {
+        Verbosity::Quiet | Verbosity::Normal => false,
+        Verbosity::Verbose => true,
+    };
+    let shown = if show_all {
+        versions.len()
+    } else {
+        versions.len().min(MAX_VERSION_PRINTS)
+    };
+    let margin = versions
+        .iter()
+        .take(shown)
+        .map(|v| v.num.len())
+        .max()
+        .unwrap_or_default();
+    writeln!(stdout, "{header}versions:{header:#}")?;
+    for version in versions.iter().take(shown) {
+        write!(
+            stdout,
+            "  {: <margin$}  {}",
+            version.num,
+            pretty_date(&version.created_at)
+        )?;
+        if let Some(publisher) = &version.published_by {
+            write!(stdout, "  {}", publisher.login)?;
+            if let Some(name) = &publisher.name {
+                write!(stdout, " ({name})")?;
+            }
+        }
+        if version.yanked {
+            write!(stdout, " {warn}(yanked){warn:#}")?;
+        }
+        writeln!(stdout)?;
+    }
+    if shown < versions.len() {
+        writeln!(
+            stdout,
+            "  {summary}{} more versions{summary:#}",
+            versions.len() - shown
+        )?;
+    }
+
+    Ok(())
+}
+
+// Warn that the package may be confused with a much more popular crate.
+pub(super) fn warn_name_confusion(
+    package_id: PackageId,
+    confusion: &NameConfusion,
+    shell: &mut Shell,
+) -> CargoResult<()> {
+    let other = &confusion.name;
+    let popularity = match confusion.popularity {
+        Popularity::Downloads(own, theirs) => format!("{theirs} downloads against {own}"),
+        Popularity::Versions(own, theirs) => {
+            format!("{theirs} published versions against {own}")
+        }
+    };
+    shell.warn(format!(
+        "`{}` has a name similar to the much more popular crate `{other}` ({popularity})",
+        package_id.name()
+    ))?;
+    shell.note(format!(
+        "make sure this is the intended crate, or run `cargo info {other}`"
+    ))?;
+    Ok(())
+}
+
+fn pretty_reverse_dependencies(
+    reverse_dependencies: &ReverseDependencies,
+    theme: &Theme,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let header = theme.header;
+    let summary = theme.summary;
+
+    let dependents = &reverse_dependencies.dependents;
+    if dependents.is_empty() {
+        writeln!(stdout, "{header}reverse-dependencies:{header:#} none")?;
+        return Ok(());
+    }
+
+    writeln!(stdout, "{header}reverse-dependencies:{header:#}")?;
+    let name_margin = dependents
+        .iter()
+        .map(|d| d.name.len() + d.version.len() + 1)
+        .max()
+        .unwrap_or_default();
+    let req_margin = dependents
+        .iter()
+        .map(|d| d.req.len())
+        .max()
+        .unwrap_or_default();
+    for dependent in dependents {
+        let name = format!("{}@{}", dependent.name, dependent.version);
+        write!(
+            stdout,
+            "  {name: <name_margin$}  {: <req_margin$}  {} downloads",
+            dependent.req, dependent.downloads
+        )?;
+        let mut notes = Vec::new();
+        if dependent.optional {
+            notes.push("optional");
+        }
+        if let Some(kind) = dependent.kind.as_deref().filter(|&k| k != "normal") {
+            notes.push(kind);
+        }
+        if !notes.is_empty() {
+            write!(stdout, " ({})", notes.join(", "))?;
+        }
+        writeln!(stdout)?;
+    }
+    let remaining = reverse_dependencies
+        .total
+        .saturating_sub(dependents.len() as u64);
+    if remaining > 0 {
+        let plural = if remaining == 1 { "" } else { "s" };
+        writeln!(
+            stdout,
+            "  {summary}{remaining} more dependent{plural}{summary:#}"
+        )?;
+    }
+
+    // Summarize which version requirements the dependents rely on.
+    let mut reqs: Vec<(&str, usize)> = Vec::new();
+    for dependent in dependents {
+        match reqs.iter_mut().find(|(req, _)| *req == dependent.req) {
+            Some((_, count)) => *count += 1,
+            None => reqs.push((&dependent.req, 1)),
         }
     }
+    reqs.sort_by(|(r1, c1), (r2, c2)| c2.cmp(c1).then_with(|| r1.cmp(r2)));
+    writeln!(
+        stdout,
+        "  requirements: {}",
+        reqs.iter()
+            .map(|(req, count)| format!("{req} ({count})"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )?;
 
     Ok(())
 }
 
+// Only keep the date of an RFC 3339 timestamp.
+pub(super) fn pretty_date(timestamp: &str) -> &str {
+    timestamp.get(..10).unwrap_or(timestamp)
+}
+
 // Suggest the cargo tree command to view the dependency tree.
-fn suggest_cargo_tree(package_id: PackageId, stdout: &mut dyn Write) -> CargoResult<()> {
-    let literal = LITERAL;
+fn suggest_cargo_tree(
+    package_id: PackageId,
+    theme: &Theme,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let literal = theme.literal;
 
     note(format_args!(
         "to see how you depend on {name}, run `{literal}cargo tree --invert --package {name}@{version}{literal:#}`",
         name = package_id.name(),
         version = package_id.version(),
-    ), stdout)
+    ), theme, stdout)
 }
 
-pub(super) fn note(msg: impl std::fmt::Display, stdout: &mut dyn Write) -> CargoResult<()> {
-    let note = NOTE;
-    let bold = anstyle::Style::new() | anstyle::Effects::BOLD;
+pub(super) fn note(
+    msg: impl std::fmt::Display,
+    theme: &Theme,
+    stdout: &mut dyn Write,
+) -> CargoResult<()> {
+    let note = theme.note;
+    let bold = theme.emphasis;
 
     writeln!(stdout, "{note}note{note:#}{bold}:{bold:#} {msg}",)?;
 
@@ -434,19 +1497,42 @@ pub(super) fn note(msg: impl std::fmt::Display, stdout: &mut dyn Write) -> Cargo
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-enum FeatureStatus {
+pub(super) enum FeatureStatus {
     EnabledByUser,
     Enabled,
     Disabled,
 }
 
 impl FeatureStatus {
-    fn is_disabled(&self) -> bool {
+    pub(super) fn is_disabled(&self) -> bool {
         *self == FeatureStatus::Disabled
     }
 }
 
-fn resolve_features(
+// Get the features explicitly activated on the command line, including `default`.
+pub(super) fn activated_features(
+    cli_features: &CliFeatures,
+    features: &FeatureMap,
+) -> Vec<InternedString> {
+    if cli_features.all_features {
+        return features.keys().cloned().collect();
+    }
+    let mut activated = cli_features
+        .features
+        .iter()
+        .filter_map(|f| match f {
+            cargo::core::FeatureValue::Feature(name) => Some(*name),
+            cargo::core::FeatureValue::Dep { .. }
+            | cargo::core::FeatureValue::DepFeature { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    if cli_features.uses_default_features {
+        activated.push(InternedString::new("default"));
+    }
+    activated
+}
+
+pub(super) fn resolve_features(
     explicit: &[InternedString],
     features: &FeatureMap,
 ) -> Vec<(InternedString, FeatureStatus)> {
@@ -0,0 +1,78 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{file, paths};
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    for ver in ["0.1.0", "0.2.0", "0.3.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+
+    let advisories = paths::root().join("advisory-db/crates/my-package");
+    std::fs::create_dir_all(&advisories).unwrap();
+    std::fs::write(
+        advisories.join("RUSTSEC-2024-0001.md"),
+        r#"```toml
+[advisory]
+id = "RUSTSEC-2024-0001"
+package = "my-package"
+date = "2024-01-01"
+cvss = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+
+[versions]
+patched = [">= 0.2.0"]
+```
+
+# Remote code execution in my-package
+
+The description of the advisory.
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        advisories.join("RUSTSEC-2024-0002.md"),
+        r#"```toml
+[advisory]
+id = "RUSTSEC-2024-0002"
+package = "my-package"
+date = "2024-02-01"
+informational = "unsound"
+
+[versions]
+patched = [">= 0.3.0"]
+```
+
+# Unsound API in my-package
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        advisories.join("RUSTSEC-2024-0003.md"),
+        r#"```toml
+[advisory]
+id = "RUSTSEC-2024-0003"
+package = "my-package"
+date = "2024-03-01"
+withdrawn = "2024-03-02"
+
+[versions]
+patched = []
+```
+
+# Withdrawn advisory
+"#,
+    )
+    .unwrap();
+
+    cargo_info()
+        .arg("my-package@0.1.0")
+        .arg("--registry=dummy-registry")
+        .arg("--advisory-db")
+        .arg(paths::root().join("advisory-db"))
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,33 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+use cargo_test_support::registry::RegistryBuilder;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    let _ = RegistryBuilder::new()
+        .alternative()
+        .no_configure_token()
+        .build();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+    // The mirror carries the same 0.1.0 but a diverging 0.2.0.
+    cargo_test_support::registry::Package::new("my-package", "0.1.0")
+        .alternative(true)
+        .publish();
+    cargo_test_support::registry::Package::new("my-package", "0.2.0")
+        .file("src/lib.rs", "// mirrored")
+        .alternative(true)
+        .publish();
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--all-registries")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,25 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{current_dir, file, Project};
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+
+    let project = Project::from_template(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,80 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("dep1", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "dep1"
+            version = "0.1.0"
+            license = "MIT"
+            edition = "2018"
+            build = "build.rs"
+            "#,
+        )
+        .file("build.rs", "fn main() {}")
+        .file("src/lib.rs", "")
+        .publish();
+    cargo_test_support::registry::Package::new("alpha", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "alpha"
+            version = "0.1.0"
+            license = "MIT OR Apache-2.0"
+            edition = "2018"
+            rust-version = "1.60.0"
+
+            [features]
+            default = ["std"]
+            std = []
+            extra = []
+
+            [dependencies]
+            dep1 = "0.1.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .dep("dep1", "0.1.0")
+        .feature("default", &["std"])
+        .feature("std", &[])
+        .feature("extra", &[])
+        .rust_version("1.60.0")
+        .publish();
+    cargo_test_support::registry::Package::new("beta", "0.2.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "beta"
+            version = "0.2.0"
+            edition = "2018"
+
+            [lib]
+            proc-macro = true
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+    cargo_test_support::registry::Package::new("beta", "0.3.0")
+        .rust_version("1.9876.0")
+        .publish();
+
+    // `dep1` is not downloaded, so it is not inspected.
+    cargo_info()
+        .arg("--compare")
+        .arg("alpha")
+        .arg("beta")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,32 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    for ver in ["0.1.0", "0.2.0", "0.2.1"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+    cargo_test_support::registry::Package::new("my-package", "0.3.0")
+        .yanked(true)
+        .publish();
+
+    // Cache the index entry of the package.
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success();
+
+    // Complete the versions from the cached index entry, without the yanked ones.
+    cargo_info()
+        .arg("--complete-spec")
+        .arg("my-package@0.2")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
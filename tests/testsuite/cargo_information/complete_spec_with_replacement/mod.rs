@@ -0,0 +1,30 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    // crates-io is replaced with `dummy-registry`.
+    init_registry_without_token();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+
+    // Cache the index entry of the package.
+    cargo_info()
+        .arg("my-package")
+        .arg("--follow-replacement")
+        .assert()
+        .success();
+
+    // Complete from the replacement, following it like `cargo info` does with the config.
+    cargo_info()
+        .arg("--complete-spec")
+        .arg("my-package@")
+        .env("CARGO_INFO_FOLLOW_REPLACEMENT", "true")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,21 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    // crates-io is replaced with `dummy-registry`.
+    init_registry_without_token();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--follow-replacement")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,21 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{compare::assert_ui, current_dir, file, project};
+
+use super::cargo_info;
+
+#[cargo_test]
+fn case() {
+    let project = project().no_manifest().build();
+    let project_root = project.root();
+
+    cargo_info()
+        .arg("--generate-man")
+        .arg("man")
+        .current_dir(&project_root)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+
+    assert_ui().subset_matches(current_dir!().join("out"), &project_root);
+}
@@ -0,0 +1,75 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{basic_manifest, file, git, project};
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    let (baz, repo) = git::new_repo("baz", |project| {
+        project
+            .file("Cargo.toml", &basic_manifest("baz", "0.1.0"))
+            .file("src/lib.rs", "")
+    });
+    let locked = repo.head().unwrap().target().unwrap();
+    baz.change_file("src/lib.rs", "pub fn baz() {}");
+    git::add(&repo);
+    git::commit(&repo);
+
+    // Fetch the new head of the branch into the local git database.
+    cargo_info()
+        .arg("baz")
+        .arg("--git")
+        .arg(baz.url().to_string())
+        .arg("--branch=master")
+        .assert()
+        .success();
+
+    let foo = project()
+        .file(
+            "Cargo.toml",
+            &format!(
+                r#"
+                    [package]
+                    name = "foo"
+                    version = "0.1.0"
+
+                    [dependencies]
+                    baz = {{ git = '{}', branch = "master" }}
+                "#,
+                baz.url()
+            ),
+        )
+        .file(
+            "Cargo.lock",
+            &format!(
+                r#"# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 3
+
+[[package]]
+name = "baz"
+version = "0.1.0"
+source = "git+{}?branch=master#{locked}"
+
+[[package]]
+name = "foo"
+version = "0.1.0"
+dependencies = [
+ "baz",
+]
+"#,
+                baz.url()
+            ),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    cargo_info()
+        .arg("baz")
+        .current_dir(foo.root())
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
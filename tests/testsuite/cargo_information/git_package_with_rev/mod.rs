@@ -0,0 +1,25 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{basic_manifest, file, git};
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    let (baz, repo) = git::new_repo("baz", |project| {
+        project
+            .file("Cargo.toml", &basic_manifest("baz", "0.1.0"))
+            .file("src/lib.rs", "")
+    });
+    let rev = repo.head().unwrap().target().unwrap();
+
+    cargo_info()
+        .arg("baz")
+        .arg("--git")
+        .arg(baz.url().to_string())
+        .arg(format!("--rev={rev}"))
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,77 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{file, project};
+
+use super::cargo_info;
+
+// The git dependency is vendored, so Cargo never fetched it into a local git database.
+#[cargo_test]
+fn case() {
+    let foo = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+
+                [dependencies]
+                baz = { git = "https://example.com/baz", branch = "master" }
+            "#,
+        )
+        .file(
+            "Cargo.lock",
+            r#"# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 3
+
+[[package]]
+name = "baz"
+version = "0.1.0"
+source = "git+https://example.com/baz?branch=master#0123456789abcdef0123456789abcdef01234567"
+
+[[package]]
+name = "foo"
+version = "0.1.0"
+dependencies = [
+ "baz",
+]
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [source."git+https://example.com/baz?branch=master"]
+                git = "https://example.com/baz"
+                branch = "master"
+                replace-with = "vendored-sources"
+
+                [source.vendored-sources]
+                directory = "vendor"
+            "#,
+        )
+        .file(
+            "vendor/baz/Cargo.toml",
+            r#"
+                [package]
+                name = "baz"
+                version = "0.1.0"
+            "#,
+        )
+        .file("vendor/baz/src/lib.rs", "")
+        .file(
+            "vendor/baz/.cargo-checksum.json",
+            r#"{"files":{},"package":null}"#,
+        )
+        .build();
+
+    cargo_info()
+        .arg("baz")
+        .arg("--follow-replacement")
+        .arg("--offline")
+        .current_dir(foo.root())
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -1,31 +1,83 @@
 use cargo_test_support::{compare::assert_ui, TestEnv};
 
+mod advisories;
+mod all_registries;
+mod audits;
+mod audits_wildcard;
 mod basic;
+mod compare;
+mod compare_not_downloaded;
+mod complete_spec;
+mod complete_spec_with_replacement;
 mod features;
 mod features_activated_over_limit;
 mod features_activated_over_limit_verbose;
 mod features_deactivated_over_limit;
+mod follow_replacement;
+mod generate_man;
 mod git_dependency;
+mod git_dependency_with_reference;
+mod git_package;
+mod git_package_behind_branch;
+mod git_package_with_rev;
+mod git_package_with_tag;
+mod git_package_without_local_db;
 mod help;
+mod interactive_without_terminal;
+mod name_confusion;
+mod name_confusion_hidden;
+mod name_confusion_sys;
+mod name_confusion_with_template;
 mod not_found;
+mod not_found_with_similar_name;
 mod path_dependency;
+mod path_package;
 mod pick_msrv_compatible_package;
 mod pick_msrv_compatible_package_within_ws;
 mod pick_msrv_compatible_package_within_ws_and_use_msrv_from_ws;
+mod provenance;
+mod reverse_deps;
+mod search;
+mod search_with_pick;
 mod specify_empty_version_with_url;
 mod specify_version_outside_ws;
 mod specify_version_with_url_but_registry_is_not_matched;
 mod specify_version_within_ws_and_conflict_with_lockfile;
 mod specify_version_within_ws_and_match_with_lockfile;
+mod stats;
+mod stats_cached_fresh_offline;
+mod stats_cached_offline;
+mod stats_with_show;
+mod transitive;
 mod transitive_dependency_within_ws;
+mod transitive_not_downloaded;
+mod transitive_with_show;
 mod verbose;
+mod with_color_preset;
 mod with_frozen_outside_ws;
 mod with_frozen_within_ws;
+mod with_hide_from_config;
+mod with_hyperlinks;
+mod with_invalid_color;
+mod with_local_registry;
 mod with_locked_outside_ws;
 mod with_locked_within_ws;
 mod with_locked_within_ws_and_pick_the_package;
+mod with_narrow_terminal;
+#[cfg(unix)]
+mod with_no_pager;
 mod with_offline;
+#[cfg(unix)]
+mod with_pager;
+#[cfg(unix)]
+mod with_pager_disabled_in_config;
+#[cfg(unix)]
+mod with_pager_precedence;
 mod with_quiet;
+mod with_show;
+mod with_template;
+mod with_template_stats;
+mod with_vendored_directory;
 mod within_ws;
 mod within_ws_and_pick_ws_package;
 mod within_ws_with_alternative_registry;
@@ -56,3 +108,23 @@ pub(crate) fn init_registry_without_token() {
         .no_configure_token()
         .build();
 }
+
+// Write a pager that leaves a marker behind when it is started, and prints its input as is.
+// Returns the paths of the pager and of the marker.
+#[cfg(unix)]
+pub(crate) fn marker_pager(name: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt as _;
+
+    use cargo_test_support::paths;
+
+    let marker = paths::root().join(format!("{name}.paged"));
+    let pager = paths::root().join(format!("{name}.sh"));
+    fs::write(
+        &pager,
+        format!("#!/bin/sh\ntouch '{}'\ncat\n", marker.display()),
+    )
+    .unwrap();
+    fs::set_permissions(&pager, fs::Permissions::from_mode(0o755)).unwrap();
+    (pager, marker)
+}
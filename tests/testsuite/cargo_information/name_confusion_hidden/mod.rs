@@ -0,0 +1,29 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    for minor in 1..=10 {
+        cargo_test_support::registry::Package::new("my-package", &format!("0.{minor}.0")).publish();
+    }
+    cargo_test_support::registry::Package::new("my-packages", "0.1.0").publish();
+
+    // Query the popular crate once to populate the local index cache.
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success();
+
+    cargo_info()
+        .arg("my-packages")
+        .arg("--registry=dummy-registry")
+        .arg("--show=links")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
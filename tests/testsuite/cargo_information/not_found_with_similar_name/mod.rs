@@ -0,0 +1,28 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+    cargo_test_support::registry::Package::new("my_package_derive", "0.1.0").publish();
+
+    // Query the crate once to populate the local index cache.
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success();
+
+    cargo_info()
+        .arg("my_pakage")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .failure()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
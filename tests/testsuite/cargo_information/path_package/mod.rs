@@ -0,0 +1,22 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{basic_manifest, file, project};
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    let project = project()
+        .no_manifest()
+        .file("crates/foo/Cargo.toml", &basic_manifest("foo", "0.1.0"))
+        .file("crates/foo/src/lib.rs", "")
+        .build();
+
+    cargo_info()
+        .arg("./crates/foo")
+        .current_dir(project.root())
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,63 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+use cargo_test_support::registry::{RegistryBuilder, Response};
+
+use super::cargo_info;
+
+#[cargo_test]
+fn case() {
+    let _reg = RegistryBuilder::new()
+        .http_api()
+        .no_configure_token()
+        .add_responder("/api/v1/crates/my-package/reverse_dependencies", |_, _| {
+            Response {
+                code: 200,
+                headers: vec![],
+                body: br#"{
+                    "dependencies": [
+                        {
+                            "version_id": 2,
+                            "req": "^0.1",
+                            "optional": true,
+                            "kind": "normal",
+                            "downloads": 200
+                        },
+                        {
+                            "version_id": 1,
+                            "req": "^0.2",
+                            "optional": false,
+                            "kind": "dev",
+                            "downloads": 1000
+                        },
+                        {
+                            "version_id": 3,
+                            "req": "^0.2",
+                            "optional": false,
+                            "kind": "normal",
+                            "downloads": 50
+                        }
+                    ],
+                    "versions": [
+                        { "id": 1, "crate": "dep-a", "num": "1.0.0" },
+                        { "id": 2, "crate": "dep-b", "num": "0.3.1" },
+                        { "id": 3, "crate": "dep-c", "num": "2.1.0" }
+                    ],
+                    "meta": { "total": 3 }
+                }"#
+                .to_vec(),
+            }
+        })
+        .build();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--reverse-deps")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,66 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+use cargo_test_support::registry::{RegistryBuilder, Response};
+
+use super::cargo_info;
+
+#[cargo_test]
+fn case() {
+    let _reg = RegistryBuilder::new()
+        .http_api()
+        .no_configure_token()
+        .add_responder("/api/v1/crates", |_, _| Response {
+            code: 200,
+            headers: vec![],
+            body: br#"{
+                "crates": [
+                    {
+                        "name": "my-package",
+                        "description": "A package for testing",
+                        "max_version": "0.2.0"
+                    },
+                    {
+                        "name": "my-package-derive",
+                        "description": null,
+                        "max_version": "0.1.0"
+                    }
+                ],
+                "meta": { "total": 3 }
+            }"#
+            .to_vec(),
+        })
+        .add_responder("/api/v1/crates/my-package", |_, _| Response {
+            code: 200,
+            headers: vec![],
+            body: br#"{
+                "crate": {
+                    "name": "my-package",
+                    "keywords": ["testing", "package"],
+                    "downloads": 12345,
+                    "recent_downloads": 678,
+                    "created_at": "2020-01-01T00:00:00.000000+00:00",
+                    "updated_at": "2024-06-01T00:00:00.000000+00:00"
+                }
+            }"#
+            .to_vec(),
+        })
+        .build();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver)
+            .rust_version("1.60.0")
+            .publish();
+    }
+    cargo_test_support::registry::Package::new("my-package-derive", "0.1.0")
+        .rust_version("9999.0.0")
+        .publish();
+
+    cargo_info()
+        .arg("--search")
+        .arg("my-package")
+        .arg("--pick=2")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,53 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+use cargo_test_support::registry::{RegistryBuilder, Response};
+
+use super::cargo_info;
+
+#[cargo_test]
+fn case() {
+    let _reg = RegistryBuilder::new()
+        .http_api()
+        .no_configure_token()
+        .add_responder("/api/v1/crates/my-package", |_, _| Response {
+            code: 200,
+            headers: vec![],
+            body: br#"{
+                "crate": {
+                    "name": "my-package",
+                    "downloads": 12345,
+                    "recent_downloads": 678,
+                    "created_at": "2020-01-01T00:00:00.000000+00:00",
+                    "updated_at": "2024-06-01T00:00:00.000000+00:00"
+                },
+                "versions": [
+                    {
+                        "num": "0.2.0",
+                        "created_at": "2024-06-01T00:00:00.000000+00:00",
+                        "yanked": false,
+                        "published_by": { "login": "alice", "name": "Alice" }
+                    },
+                    {
+                        "num": "0.1.0",
+                        "created_at": "2020-01-01T00:00:00.000000+00:00",
+                        "yanked": true,
+                        "published_by": null
+                    }
+                ]
+            }"#
+            .to_vec(),
+        })
+        .build();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver).publish();
+    }
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--show=header,stats")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
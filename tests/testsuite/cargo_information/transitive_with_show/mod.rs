@@ -0,0 +1,83 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("dep1", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "dep1"
+            version = "0.1.0"
+            license = "MIT"
+            edition = "2018"
+            rust-version = "1.60.0"
+            build = "build.rs"
+            "#,
+        )
+        .file("build.rs", "fn main() {}")
+        .file("src/lib.rs", "")
+        .rust_version("1.60.0")
+        .publish();
+    cargo_test_support::registry::Package::new("dep2", "0.2.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "dep2"
+            version = "0.2.0"
+            license = "MIT OR Apache-2.0"
+            edition = "2018"
+
+            [dependencies]
+            dep1 = "0.1.0"
+
+            [lib]
+            proc-macro = true
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .dep("dep1", "0.1.0")
+        .publish();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "my-package"
+            version = "0.1.0"
+            license = "MIT"
+            edition = "2018"
+
+            [dependencies]
+            dep1 = "0.1.0"
+            dep2 = "0.2.0"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .dep("dep1", "0.1.0")
+        .dep("dep2", "0.2.0")
+        .publish();
+
+    // The dependencies are only inspected once downloaded.
+    for dep in ["dep1", "dep2"] {
+        cargo_info()
+            .arg(dep)
+            .arg("--registry=dummy-registry")
+            .assert()
+            .success();
+    }
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--show=header,transitive")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
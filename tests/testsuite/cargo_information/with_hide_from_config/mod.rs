@@ -0,0 +1,47 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{file, paths};
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    let root = paths::root();
+    std::fs::create_dir_all(root.join(".cargo")).unwrap();
+    std::fs::write(
+        root.join(".cargo/config.toml"),
+        r#"
+            [info]
+            hide = ["links"]
+        "#,
+    )
+    .unwrap();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "my-package"
+            version = "0.1.0"
+            documentation = "https://docs.rs/my-package/0.1.0"
+            license = "MIT"
+
+            [features]
+            default = ["feature1"]
+            feature1 = []
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+
+    // `--hide` adds to the sections hidden in the config.
+    cargo_info()
+        .arg("my-package")
+        .arg("--hide=features")
+        .arg("--registry=dummy-registry")
+        .current_dir(&root)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
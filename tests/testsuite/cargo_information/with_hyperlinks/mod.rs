@@ -0,0 +1,37 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info_with_color, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0")
+        .file(
+            "Cargo.toml",
+            r#"
+            [package]
+            name = "my-package"
+            version = "0.1.0"
+            documentation = "https://docs.rs/my-package"
+
+            [dependencies]
+            foo = "0.1.0"
+            bar = { version = "0.2.0", registry-index = "https://example.com/index" }
+            baz = { git = "https://github.com/rust-lang/baz", branch = "main" }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .publish();
+
+    // Only the dependencies from crates.io or git are linked, other registries have no known
+    // web page.
+    cargo_info_with_color()
+        .arg("my-package")
+        .arg("--show=links,deps")
+        .arg("--registry=dummy-registry")
+        .env("CARGO_TERM_HYPERLINKS", "true")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"]);
+}
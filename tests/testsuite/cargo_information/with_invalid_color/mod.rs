@@ -0,0 +1,19 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0").publish();
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .arg("--config=info.colors.header='blu bold'")
+        .assert()
+        .failure()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,36 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{file, paths};
+
+use super::cargo_info;
+
+#[cargo_test]
+fn case() {
+    let root = paths::root();
+    std::fs::create_dir_all(root.join(".cargo")).unwrap();
+    std::fs::write(
+        root.join(".cargo/config.toml"),
+        r#"
+            [source.crates-io]
+            replace-with = "my-local-registry"
+
+            [source.my-local-registry]
+            local-registry = "registry"
+        "#,
+    )
+    .unwrap();
+    for ver in ["0.1.0", "0.2.0"] {
+        cargo_test_support::registry::Package::new("my-package", ver)
+            .local(true)
+            .publish();
+    }
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--follow-replacement")
+        .arg("--offline")
+        .current_dir(&root)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
@@ -0,0 +1,31 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    let mut package = cargo_test_support::registry::Package::new("my-package", "0.1.0");
+    package.feature(
+        "default",
+        &["alpha", "beta", "gamma", "delta", "epsilon", "zeta"],
+    );
+    for feature in ["alpha", "beta", "gamma", "delta", "epsilon", "zeta"] {
+        package.feature(feature, &[]);
+    }
+    for dep in 'a'..='l' {
+        package.dep(&format!("dep-{dep}"), "1.0.0");
+    }
+    package.publish();
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--show=features,deps")
+        .arg("--registry=dummy-registry")
+        .env("__CARGO_TEST_TTY_WIDTH_DO_NOT_USE_THIS", "40")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
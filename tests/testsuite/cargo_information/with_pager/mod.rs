@@ -0,0 +1,23 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token, marker_pager};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0").publish();
+    let (pager, marker) = marker_pager("pager");
+
+    // stdout is not a terminal, so the pager is never started.
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .env("CARGO_PAGER", &pager)
+        .env("PAGER", &pager)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+    assert!(!marker.exists());
+}
@@ -0,0 +1,23 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token, marker_pager};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0").publish();
+    let (pager, marker) = marker_pager("pager");
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .env("__CARGO_TEST_FORCE_PAGER_DO_NOT_USE_THIS", "1")
+        .env("CARGO_INFO_PAGER", "false")
+        .env("PAGER", &pager)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+    assert!(!marker.exists());
+}
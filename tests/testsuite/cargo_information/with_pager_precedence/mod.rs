@@ -0,0 +1,26 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token, marker_pager};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0").publish();
+    let (cargo_pager, cargo_pager_marker) = marker_pager("cargo-pager");
+    let (pager, pager_marker) = marker_pager("pager");
+
+    // `CARGO_PAGER` is preferred over `PAGER`, and the output goes through it.
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .env("__CARGO_TEST_FORCE_PAGER_DO_NOT_USE_THIS", "1")
+        .env("CARGO_PAGER", &cargo_pager)
+        .env("PAGER", &pager)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+    assert!(cargo_pager_marker.exists());
+    assert!(!pager_marker.exists());
+}
@@ -0,0 +1,24 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0")
+        .feature("default", &["feature1"])
+        .feature("feature1", &[])
+        .dep("foo", "0.1.0")
+        .publish();
+
+    // Dependencies are shown without `--verbose` when explicitly asked for.
+    cargo_info()
+        .arg("my-package")
+        .arg("--show=features,deps")
+        .arg("--registry=dummy-registry")
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
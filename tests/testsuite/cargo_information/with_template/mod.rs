@@ -0,0 +1,27 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::file;
+
+use super::{cargo_info, init_registry_without_token};
+
+#[cargo_test]
+fn case() {
+    init_registry_without_token();
+    cargo_test_support::registry::Package::new("my-package", "0.1.0")
+        .rust_version("1.70")
+        .feature("default", &["feature1"])
+        .feature("feature1", &[])
+        .feature("feature2", &[])
+        .publish();
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--registry=dummy-registry")
+        .arg("--template")
+        .arg(
+            r"{name}@{version} msrv={rust_version} features={features}\n{#features}- {name} = [{values}]{#enabled} (enabled){/enabled}\n{/features}",
+        )
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}
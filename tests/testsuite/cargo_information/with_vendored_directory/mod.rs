@@ -0,0 +1,21 @@
+use cargo_test_macro::cargo_test;
+use cargo_test_support::{current_dir, file, Project};
+
+use super::cargo_info;
+
+#[cargo_test]
+fn case() {
+    let project = Project::from_template(current_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    cargo_info()
+        .arg("my-package")
+        .arg("--follow-replacement")
+        .arg("--offline")
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_eq_(file!["stdout.log"])
+        .stderr_eq_(file!["stderr.log"]);
+}